@@ -0,0 +1,48 @@
+//! Compares the CPU cost of the two ways of drawing a `WindowBuffer`, from
+//! laying out the frame to tessellating its shapes, on a Snake sized board.
+//!
+//! `cargo bench --bench draw_window_buffer`
+
+use std::time::{Duration, Instant};
+
+use resume::{draw_window_buffer, draw_window_buffer_rects, BoardLayout};
+use window_rs::WindowBuffer;
+
+const FRAMES: u32 = 500;
+
+fn main() {
+    for (name, changing) in [("static board", false), ("one cell changing", true)] {
+        println!("{name}:");
+        let rects = bench(draw_window_buffer_rects, changing);
+        let texture = bench(draw_window_buffer, changing);
+        println!("  rectangles: {:?} per frame", rects);
+        println!("  texture:    {:?} per frame", texture);
+    }
+}
+
+/// Average time to draw and tessellate a frame showing a 80x50 buffer.
+fn bench(draw: fn(&mut egui::Ui, &WindowBuffer) -> BoardLayout, changing: bool) -> Duration {
+    let ctx = egui::Context::default();
+    let mut buffer = WindowBuffer::new(80, 50);
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(1280.0, 800.0),
+        )),
+        ..Default::default()
+    };
+
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        if changing {
+            let cell = frame as usize % (80 * 50);
+            buffer[(cell % 80, cell / 80)] = 0x0033CCFF;
+        }
+        let output = ctx.run(input.clone(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| draw(ui, &buffer));
+        });
+        let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+        std::hint::black_box(primitives);
+    }
+    start.elapsed() / FRAMES
+}
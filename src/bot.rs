@@ -0,0 +1,475 @@
+//! Computer players steering the second snake through player 2's actions.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+type Cell = (usize, usize);
+
+/// How the computer chooses where the second snake goes.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Strategy {
+    /// Straight towards the food, only avoiding the next cell.
+    Greedy,
+    /// The shortest way to the food after which it can still reach its tail.
+    #[default]
+    SafePath,
+    /// Along a cycle through every cell of the board, slow but never stuck.
+    Hamiltonian,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Greedy, Strategy::SafePath, Strategy::Hamiltonian];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::Greedy => "Greedy",
+            Strategy::SafePath => "Safe path",
+            Strategy::Hamiltonian => "Hamiltonian cycle",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    const ALL: [Move; 4] = [Move::Up, Move::Down, Move::Left, Move::Right];
+
+    /// The player 2 action steering the second snake this way.
+    pub fn action(self) -> graphic::Key {
+        match self {
+            Move::Up => graphic::Key::UpPlayer2,
+            Move::Down => graphic::Key::DownPlayer2,
+            Move::Left => graphic::Key::LeftPlayer2,
+            Move::Right => graphic::Key::RightPlayer2,
+        }
+    }
+}
+
+/// What the computer sees of the board.
+pub struct Board<'a> {
+    pub width: usize,
+    pub height: usize,
+    /// Whether the snakes go through the walls, as in ghost mode.
+    pub wrap: bool,
+    /// The snake steered, head first.
+    pub snake: &'a [Cell],
+    /// The cells of the other snakes.
+    pub obstacles: &'a [Cell],
+    /// The berries ending the game when eaten.
+    pub bad_berries: &'a [Cell],
+    pub food: Cell,
+}
+
+impl Board<'_> {
+    /// The cell next to `cell` in the direction of `step`, `None` past a wall.
+    fn next(&self, (x, y): Cell, step: Move) -> Option<Cell> {
+        let (x, y) = (x as isize, y as isize);
+        let (x, y) = match step {
+            Move::Up => (x, y - 1),
+            Move::Down => (x, y + 1),
+            Move::Left => (x - 1, y),
+            Move::Right => (x + 1, y),
+        };
+        let (width, height) = (self.width as isize, self.height as isize);
+        if self.wrap {
+            Some((x.rem_euclid(width) as usize, y.rem_euclid(height) as usize))
+        } else if (0..width).contains(&x) && (0..height).contains(&y) {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    fn index(&self, (x, y): Cell) -> usize {
+        y * self.width + x
+    }
+
+    /// The cells taken by `snake`, the obstacles and the bad berries, but the
+    /// tail of the snake which moves away on the next tick, unless it is right
+    /// behind the head: the snake can't turn back.
+    fn blocked(&self, snake: &[Cell]) -> Vec<bool> {
+        let mut blocked = vec![false; self.width * self.height];
+        let body = &snake[..snake.len().saturating_sub(1)];
+        let neck = snake.get(1);
+        for cell in body
+            .iter()
+            .chain(neck)
+            .chain(self.obstacles)
+            .chain(self.bad_berries)
+        {
+            blocked[self.index(*cell)] = true;
+        }
+        blocked
+    }
+
+    /// The moves of the shortest way from `from` to `to` around `blocked`.
+    fn path(&self, from: Cell, to: Cell, blocked: &[bool]) -> Option<Vec<Move>> {
+        let mut came_from: Vec<Option<(Cell, Move)>> = vec![None; self.width * self.height];
+        let mut queue = VecDeque::from([from]);
+        let mut seen = vec![false; self.width * self.height];
+        seen[self.index(from)] = true;
+        while let Some(cell) = queue.pop_front() {
+            if cell == to {
+                let mut moves = Vec::new();
+                let mut cell = to;
+                while let Some((previous, step)) = came_from[self.index(cell)] {
+                    moves.push(step);
+                    cell = previous;
+                }
+                moves.reverse();
+                return Some(moves);
+            }
+            for step in Move::ALL {
+                let Some(next) = self.next(cell, step) else {
+                    continue;
+                };
+                let i = self.index(next);
+                if !seen[i] && (!blocked[i] || next == to) {
+                    seen[i] = true;
+                    came_from[i] = Some((cell, step));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Number of free cells reachable from `from`.
+    fn room(&self, from: Cell, blocked: &[bool]) -> usize {
+        let mut seen = blocked.to_vec();
+        let mut stack = vec![from];
+        let mut room = 0;
+        while let Some(cell) = stack.pop() {
+            for next in Move::ALL.iter().filter_map(|step| self.next(cell, *step)) {
+                let i = self.index(next);
+                if !seen[i] {
+                    seen[i] = true;
+                    room += 1;
+                    stack.push(next);
+                }
+            }
+        }
+        room
+    }
+
+    /// The snake after following `moves`, growing on the food.
+    fn moved(&self, moves: &[Move]) -> Vec<Cell> {
+        let mut snake = self.snake.to_vec();
+        for step in moves {
+            let Some(head) = self.next(snake[0], *step) else {
+                break;
+            };
+            snake.insert(0, head);
+            if head != self.food {
+                snake.pop();
+            }
+        }
+        snake
+    }
+
+    /// Whether the head of `snake` can still reach its tail.
+    fn tail_reachable(&self, snake: &[Cell]) -> bool {
+        let tail = snake[snake.len() - 1];
+        snake.len() < 2 || self.path(snake[0], tail, &self.blocked(snake)).is_some()
+    }
+
+    /// The moves not hitting a wall or a snake on the next tick.
+    fn safe_moves(&self) -> Vec<(Move, Cell)> {
+        let blocked = self.blocked(self.snake);
+        Move::ALL
+            .into_iter()
+            .filter_map(|step| Some((step, self.next(self.snake[0], step)?)))
+            .filter(|(_, cell)| !blocked[self.index(*cell)])
+            .collect()
+    }
+
+    fn distance(&self, (ax, ay): Cell, (bx, by): Cell) -> usize {
+        let (dx, dy) = (ax.abs_diff(bx), ay.abs_diff(by));
+        if self.wrap {
+            dx.min(self.width - dx) + dy.min(self.height - dy)
+        } else {
+            dx + dy
+        }
+    }
+}
+
+/// A computer player, keeping what it computed for the board between ticks.
+pub struct Bot {
+    pub strategy: Strategy,
+    /// The position of each cell in the Hamiltonian cycle of a board of
+    /// this size.
+    cycle: Option<((usize, usize), Vec<usize>)>,
+}
+
+impl Bot {
+    pub fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            cycle: None,
+        }
+    }
+
+    /// Where the snake goes next, `None` when it is trapped.
+    pub fn next_move(&mut self, board: &Board) -> Option<Move> {
+        if board.snake.is_empty() {
+            return None;
+        }
+        match self.strategy {
+            Strategy::Greedy => greedy(board),
+            Strategy::SafePath => safe_path(board),
+            Strategy::Hamiltonian => self.hamiltonian(board),
+        }
+    }
+
+    /// The next cell of the cycle when it is free, otherwise a safe path
+    /// until the snake is back on the cycle.
+    fn hamiltonian(&mut self, board: &Board) -> Option<Move> {
+        let size = (board.width, board.height);
+        if self.cycle.as_ref().map(|(cached, _)| *cached) != Some(size) {
+            self.cycle = hamiltonian_cycle(board.width, board.height).map(|cycle| (size, cycle));
+        }
+        let Some((_, order)) = &self.cycle else {
+            return safe_path(board);
+        };
+        let head = order[board.index(board.snake[0])];
+        let next = (head + 1) % order.len();
+        board
+            .safe_moves()
+            .into_iter()
+            .find(|(_, cell)| order[board.index(*cell)] == next)
+            .map(|(step, _)| step)
+            .or_else(|| safe_path(board))
+    }
+}
+
+fn greedy(board: &Board) -> Option<Move> {
+    board
+        .safe_moves()
+        .into_iter()
+        .min_by_key(|(_, cell)| board.distance(*cell, board.food))
+        .map(|(step, _)| step)
+}
+
+/// Follows the shortest path to the food when the snake can reach its tail
+/// once there, otherwise chases its tail, otherwise goes where there is the
+/// most room.
+fn safe_path(board: &Board) -> Option<Move> {
+    let blocked = board.blocked(board.snake);
+    if let Some(path) = board.path(board.snake[0], board.food, &blocked) {
+        if !path.is_empty() && board.tail_reachable(&board.moved(&path)) {
+            return Some(path[0]);
+        }
+    }
+
+    let safe = board.safe_moves();
+    let tail = board.snake[board.snake.len() - 1];
+    let chasing = safe
+        .iter()
+        .filter(|(step, _)| board.tail_reachable(&board.moved(&[*step])))
+        .max_by_key(|(_, cell)| board.distance(*cell, tail));
+    if let Some((step, _)) = chasing {
+        return Some(*step);
+    }
+
+    safe.into_iter()
+        .max_by_key(|(step, _)| {
+            let moved = board.moved(&[*step]);
+            board.room(moved[0], &board.blocked(&moved))
+        })
+        .map(|(step, _)| step)
+}
+
+/// The position of each cell in a cycle going through the whole board, row
+/// by row and back up the first column. There is none when both sides are odd.
+fn hamiltonian_cycle(width: usize, height: usize) -> Option<Vec<usize>> {
+    if width < 2 || height < 2 || width * height % 2 == 1 {
+        return None;
+    }
+    if height % 2 == 1 {
+        // Column by column instead, transposing the cycle of the rotated board.
+        let transposed = hamiltonian_cycle(height, width)?;
+        let mut order = vec![0; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                order[y * width + x] = transposed[x * height + y];
+            }
+        }
+        return Some(order);
+    }
+
+    let mut cells = Vec::with_capacity(width * height);
+    for y in 0..height {
+        if y % 2 == 0 {
+            cells.extend((1..width).map(|x| (x, y)));
+        } else {
+            cells.extend((1..width).rev().map(|x| (x, y)));
+        }
+    }
+    cells.extend((0..height).rev().map(|y| (0, y)));
+
+    let mut order = vec![0; width * height];
+    for (i, (x, y)) in cells.into_iter().enumerate() {
+        order[y * width + x] = i;
+    }
+    Some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays a lone snake on an empty board with food drawn from `seed`,
+    /// returning its length at the end or the tick it died at.
+    fn play(
+        strategy: Strategy,
+        width: usize,
+        height: usize,
+        seed: u64,
+        ticks: usize,
+    ) -> Result<usize, usize> {
+        let mut rng = seed;
+        let mut random = move |bound: usize| {
+            rng = rng
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (rng >> 33) as usize % bound
+        };
+        let mut snake = vec![(3, 1), (2, 1), (1, 1)];
+        let mut bot = Bot::new(strategy);
+        let mut food = (width - 2, height - 2);
+        for tick in 0..ticks {
+            if snake.len() == width * height {
+                return Ok(snake.len());
+            }
+            let board = Board {
+                width,
+                height,
+                wrap: false,
+                snake: &snake,
+                obstacles: &[],
+                bad_berries: &[],
+                food,
+            };
+            let step = bot.next_move(&board).ok_or(tick)?;
+            let head = board.next(snake[0], step).ok_or(tick)?;
+            if snake[..snake.len() - 1].contains(&head) {
+                return Err(tick);
+            }
+            snake.insert(0, head);
+            if head == food {
+                let free: Vec<_> = (0..width * height)
+                    .map(|i| (i % width, i / width))
+                    .filter(|cell| !snake.contains(cell))
+                    .collect();
+                if free.is_empty() {
+                    return Ok(snake.len());
+                }
+                food = free[random(free.len())];
+            } else {
+                snake.pop();
+            }
+        }
+        Ok(snake.len())
+    }
+
+    #[test]
+    fn safe_path_never_dies_on_an_empty_board() {
+        for seed in 0..20 {
+            let length = play(Strategy::SafePath, 12, 10, seed, 5000);
+            assert!(
+                matches!(length, Ok(length) if length > 30),
+                "seed {seed}: {length:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn hamiltonian_never_dies_on_an_empty_board() {
+        for (width, height) in [(8, 6), (7, 6), (6, 7)] {
+            let length = play(Strategy::Hamiltonian, width, height, 1, 5000);
+            assert!(
+                matches!(length, Ok(length) if length > 30),
+                "{width}x{height}: {length:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn hamiltonian_cycle_visits_every_cell_once() {
+        for (width, height) in [(4, 4), (5, 4), (4, 5), (80, 50)] {
+            let order = hamiltonian_cycle(width, height).unwrap();
+            let mut sorted = order.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..width * height).collect::<Vec<_>>());
+            // Consecutive cells of the cycle are neighbours.
+            let mut cells = vec![(0, 0); width * height];
+            for (i, position) in order.iter().enumerate() {
+                cells[*position] = (i % width, i / width);
+            }
+            for (i, (x, y)) in cells.iter().enumerate() {
+                let (nx, ny) = cells[(i + 1) % cells.len()];
+                assert_eq!(
+                    x.abs_diff(nx) + y.abs_diff(ny),
+                    1,
+                    "{width}x{height} at {i}"
+                );
+            }
+        }
+        assert!(hamiltonian_cycle(5, 5).is_none());
+    }
+
+    #[test]
+    fn greedy_goes_towards_the_food() {
+        let board = Board {
+            width: 10,
+            height: 10,
+            wrap: false,
+            snake: &[(5, 5), (4, 5)],
+            obstacles: &[],
+            bad_berries: &[],
+            food: (5, 1),
+        };
+        assert_eq!(greedy(&board), Some(Move::Up));
+    }
+
+    #[test]
+    fn short_snakes_do_not_turn_back() {
+        let board = Board {
+            width: 10,
+            height: 10,
+            wrap: false,
+            snake: &[(5, 5), (4, 5)],
+            obstacles: &[],
+            bad_berries: &[],
+            food: (1, 5),
+        };
+        for strategy in Strategy::ALL {
+            let step = Bot::new(strategy).next_move(&board);
+            assert!(matches!(step, Some(step) if step != Move::Left), "{step:?}");
+        }
+    }
+
+    #[test]
+    fn bad_berries_are_avoided() {
+        let board = Board {
+            width: 10,
+            height: 10,
+            wrap: false,
+            snake: &[(5, 5), (5, 6), (5, 7)],
+            obstacles: &[],
+            bad_berries: &[(5, 4)],
+            food: (5, 1),
+        };
+        for strategy in [Strategy::Greedy, Strategy::SafePath] {
+            let step = Bot::new(strategy).next_move(&board);
+            assert!(matches!(step, Some(step) if step != Move::Up), "{step:?}");
+        }
+    }
+}
@@ -1,136 +1,129 @@
 use egui::{
     color_picker::{color_edit_button_rgba, Alpha},
-    Color32, InputState, Key, Pos2, Rgba, Rounding, Sense, Ui, Vec2,
+    Color32, ColorImage, InputState, Pos2, Rect, Rgba, Rounding, Sense, TextureHandle, TextureId,
+    TextureOptions, Ui, Vec2,
 };
 use graphic::Graphic;
+use std::{
+    cell::Cell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+use web_time::{Duration, Instant};
 use window_rs::WindowBuffer;
 
-pub struct InputWrapper<'a> {
+use crate::keymap::{ActionFrame, Keymap, ACTIONS};
+
+/// What the games read from a [`Graphic`] each frame. Unlike `Graphic`, whose
+/// `new` returns `Self`, it can be used as `dyn GameInput`, so games take the
+/// input of the app and of [`crate::Headless`] alike.
+pub trait GameInput {
+    fn is_key_down(&self, key: graphic::Key) -> bool;
+
+    fn is_key_pressed(&self, key: graphic::Key) -> bool;
+
+    fn get_keys_released(&self) -> Vec<graphic::Key>;
+
+    /// The `(x, y)` cell clicked with `mouse` this frame.
+    fn get_mouse_pos(&self, mouse: graphic::Mouse) -> Option<(f32, f32)>;
+
+    fn get_mouse_down(&self, mouse: graphic::Mouse) -> bool;
+}
+
+/// The input of an egui frame.
+pub struct FrameInput<'a> {
     pub input: &'a InputState,
+    /// The keyboard key of each action.
+    pub keymap: &'a Keymap,
+    /// The actions triggered by the game controllers and the touch controls.
+    pub actions: &'a ActionFrame,
+    /// Where the board was last drawn, to give mouse positions in cells.
+    pub layout: Option<BoardLayout>,
 }
 
-impl Graphic for InputWrapper<'_> {
-    fn new(_name: &str, _width: usize, _height: usize) -> Self {
-        todo!()
+/// A [`GameInput`] as the [`Graphic`] the game crates read their input from.
+#[derive(Clone, Copy)]
+pub struct InputWrapper<'a> {
+    input: &'a dyn GameInput,
+}
+
+impl<'a> InputWrapper<'a> {
+    pub fn wrap(input: &'a dyn GameInput) -> Self {
+        Self { input }
     }
+}
 
-    fn is_open(&self) -> bool {
-        true
+/// Where [`draw_window_buffer`] drew a [`WindowBuffer`] on the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoardLayout {
+    /// Screen position of the top left corner of the cell `(0, 0)`.
+    pub origin: Pos2,
+    /// Side of a cell, in points.
+    pub cell_size: f32,
+    /// The whole board on the screen.
+    pub rect: Rect,
+}
+
+impl BoardLayout {
+    /// The layout of the board drawn in the previous frame.
+    pub fn last(ctx: &egui::Context) -> Option<Self> {
+        ctx.data(|d| d.get_temp(Self::id()))
     }
 
-    fn is_key_down(&self, key: graphic::Key) -> bool {
-        match key {
-            graphic::Key::Up => self.input.key_down(Key::ArrowUp),
-            graphic::Key::Down => self.input.key_down(Key::ArrowDown),
-            graphic::Key::Left => self.input.key_down(Key::ArrowLeft),
-            graphic::Key::Right => self.input.key_down(Key::ArrowRight),
-            graphic::Key::Escape => self.input.key_down(Key::Escape),
-            graphic::Key::Quit => self.input.key_down(Key::Delete),
-            graphic::Key::Space => self.input.key_down(Key::Space),
-            graphic::Key::UpPlayer1 => self.input.key_down(Key::E),
-            graphic::Key::DownPlayer1 => self.input.key_down(Key::D),
-            graphic::Key::UpPlayer2 => self.input.key_down(Key::O),
-            graphic::Key::DownPlayer2 => self.input.key_down(Key::L),
-            graphic::Key::LeftPlayer2 => self.input.key_down(Key::K),
-            graphic::Key::RightPlayer2 => self.input.key_down(Key::M),
-            graphic::Key::Launch => self.input.key_down(Key::W),
-            graphic::Key::Forward => self.input.key_down(Key::F),
-            graphic::Key::Backward => self.input.key_down(Key::R),
-            graphic::Key::Save => self.input.key_down(Key::S),
+    /// Keeps the layout of the board drawn this frame for [`BoardLayout::last`].
+    pub fn store(self, ctx: &egui::Context) {
+        ctx.data_mut(|d| d.insert_temp(Self::id(), self));
+    }
+
+    fn id() -> egui::Id {
+        egui::Id::new("board_layout")
+    }
+
+    /// The `(x, y)` cell under `pos`, `None` outside of the board. The right
+    /// and bottom edges belong to the cells beyond, which don't exist.
+    pub fn cell_at(&self, pos: Pos2) -> Option<(usize, usize)> {
+        if self.cell_size <= 0.0 || pos.x < self.rect.min.x || pos.y < self.rect.min.y {
+            return None;
         }
+        let cell = (pos - self.origin) / self.cell_size;
+        let (x, y) = (cell.x as usize, cell.y as usize);
+        let width = (self.rect.width() / self.cell_size).round() as usize;
+        let height = (self.rect.height() / self.cell_size).round() as usize;
+        (x < width && y < height).then_some((x, y))
     }
+}
 
-    fn update_with_buffer(&mut self, _windows: &WindowBuffer) {
-        todo!()
+impl GameInput for FrameInput<'_> {
+    fn is_key_down(&self, key: graphic::Key) -> bool {
+        self.input.key_down(self.keymap.key(&key)) || self.actions.is_down(&key)
     }
 
     fn is_key_pressed(&self, key: graphic::Key) -> bool {
-        match key {
-            graphic::Key::Up => self.input.key_pressed(Key::ArrowUp),
-            graphic::Key::Down => self.input.key_pressed(Key::ArrowDown),
-            graphic::Key::Left => self.input.key_pressed(Key::ArrowLeft),
-            graphic::Key::Right => self.input.key_pressed(Key::ArrowRight),
-            graphic::Key::Escape => self.input.key_pressed(Key::Escape),
-            graphic::Key::Quit => self.input.key_pressed(Key::Delete),
-            graphic::Key::Space => self.input.key_pressed(Key::Space),
-            graphic::Key::UpPlayer1 => self.input.key_pressed(Key::E),
-            graphic::Key::DownPlayer1 => self.input.key_pressed(Key::D),
-            graphic::Key::UpPlayer2 => self.input.key_pressed(Key::O),
-            graphic::Key::DownPlayer2 => self.input.key_pressed(Key::L),
-            graphic::Key::LeftPlayer2 => self.input.key_pressed(Key::K),
-            graphic::Key::RightPlayer2 => self.input.key_pressed(Key::M),
-            graphic::Key::Launch => self.input.key_pressed(Key::W),
-            graphic::Key::Forward => self.input.key_pressed(Key::F),
-            graphic::Key::Backward => self.input.key_pressed(Key::R),
-            graphic::Key::Save => self.input.key_pressed(Key::S),
-        }
+        self.input.key_pressed(self.keymap.key(&key)) || self.actions.is_pressed(&key)
     }
 
     fn get_keys_released(&self) -> Vec<graphic::Key> {
-        let mut ret = vec![];
-        if self.input.key_released(Key::ArrowUp) {
-            ret.push(graphic::Key::Up)
-        }
-        if self.input.key_released(Key::ArrowDown) {
-            ret.push(graphic::Key::Down)
-        }
-        if self.input.key_released(Key::ArrowLeft) {
-            ret.push(graphic::Key::Left)
-        }
-        if self.input.key_released(Key::ArrowRight) {
-            ret.push(graphic::Key::Right)
-        }
-        if self.input.key_released(Key::Escape) {
-            ret.push(graphic::Key::Escape)
-        }
-        if self.input.key_released(Key::Delete) {
-            ret.push(graphic::Key::Quit)
-        }
-        if self.input.key_released(Key::Space) {
-            ret.push(graphic::Key::Space)
-        }
-        if self.input.key_released(Key::E) {
-            ret.push(graphic::Key::UpPlayer1)
-        }
-        if self.input.key_released(Key::D) {
-            ret.push(graphic::Key::DownPlayer1)
-        }
-        if self.input.key_released(Key::O) {
-            ret.push(graphic::Key::UpPlayer2)
-        }
-        if self.input.key_released(Key::L) {
-            ret.push(graphic::Key::DownPlayer2)
-        }
-        if self.input.key_released(Key::K) {
-            ret.push(graphic::Key::LeftPlayer2)
-        }
-        if self.input.key_released(Key::M) {
-            ret.push(graphic::Key::RightPlayer2)
-        }
-        if self.input.key_released(Key::W) {
-            ret.push(graphic::Key::Launch)
-        }
-        if self.input.key_released(Key::F) {
-            ret.push(graphic::Key::Forward)
-        }
-        if self.input.key_released(Key::R) {
-            ret.push(graphic::Key::Backward)
-        }
-        if self.input.key_released(Key::S) {
-            ret.push(graphic::Key::Save)
-        }
-        ret
+        ACTIONS
+            .into_iter()
+            .filter(|action| {
+                self.input.key_released(self.keymap.key(action)) || self.actions.is_released(action)
+            })
+            .collect()
     }
 
+    /// The cell clicked with `mouse` when the board layout is known, otherwise
+    /// the position on the screen.
     fn get_mouse_pos(&self, mouse: graphic::Mouse) -> Option<(f32, f32)> {
         let ret = self.input.pointer.interact_pos()?;
-        let retured_pos = (ret[0], ret[1]);
-        let clicked = match mouse {
-            graphic::Mouse::Left => self.input.pointer.primary_clicked(),
-            graphic::Mouse::Right => self.input.pointer.secondary_clicked(),
-            graphic::Mouse::Discard => self.input.pointer.secondary_clicked(),
+        let retured_pos = match self.layout {
+            Some(layout) => {
+                let (x, y) = layout.cell_at(ret)?;
+                (x as f32, y as f32)
+            }
+            None => (ret[0], ret[1]),
         };
-        if clicked {
+        if self.get_mouse_down(mouse) {
             Some(retured_pos)
         } else {
             None
@@ -146,41 +139,326 @@ impl Graphic for InputWrapper<'_> {
     }
 }
 
-pub fn draw_window_buffer(ui: &mut egui::Ui, window: &WindowBuffer) {
+/// The input of [`InputWrapper::new`], where nothing is ever pressed.
+struct NoInput;
+
+impl GameInput for NoInput {
+    fn is_key_down(&self, _key: graphic::Key) -> bool {
+        false
+    }
+
+    fn is_key_pressed(&self, _key: graphic::Key) -> bool {
+        false
+    }
+
+    fn get_keys_released(&self) -> Vec<graphic::Key> {
+        Vec::new()
+    }
+
+    fn get_mouse_pos(&self, _mouse: graphic::Mouse) -> Option<(f32, f32)> {
+        None
+    }
+
+    fn get_mouse_down(&self, _mouse: graphic::Mouse) -> bool {
+        false
+    }
+}
+
+impl Graphic for InputWrapper<'_> {
+    /// An input where nothing is pressed, the games are given the input of a
+    /// frame with [`InputWrapper::wrap`].
+    fn new(_name: &str, _width: usize, _height: usize) -> Self {
+        Self { input: &NoInput }
+    }
+
+    fn is_open(&self) -> bool {
+        true
+    }
+
+    fn is_key_down(&self, key: graphic::Key) -> bool {
+        self.input.is_key_down(key)
+    }
+
+    /// Does nothing, [`crate::Game::ui`] draws the buffer after the tick.
+    fn update_with_buffer(&mut self, _windows: &WindowBuffer) {}
+
+    fn is_key_pressed(&self, key: graphic::Key) -> bool {
+        self.input.is_key_pressed(key)
+    }
+
+    fn get_keys_released(&self) -> Vec<graphic::Key> {
+        self.input.get_keys_released()
+    }
+
+    fn get_mouse_pos(&self, mouse: graphic::Mouse) -> Option<(f32, f32)> {
+        self.input.get_mouse_pos(mouse)
+    }
+
+    fn get_mouse_down(&self, mouse: graphic::Mouse) -> bool {
+        self.input.get_mouse_down(mouse)
+    }
+}
+
+/// Draws `window` as a texture scaled to fit the available space, uploaded
+/// again only when its content changed.
+pub fn draw_window_buffer(ui: &mut egui::Ui, window: &WindowBuffer) -> BoardLayout {
     let max_width = ui.available_width();
     let max_height = ui.available_height();
     let size = (max_width / window.width() as f32).min(max_height / window.height() as f32);
-    egui::ScrollArea::both().show(ui, |ui| {
-        let (response, painter) =
-            ui.allocate_painter(Vec2::new(max_width, ui.available_height()), Sense::hover());
+    let texture = buffer_texture(ui.ctx(), ui.id().with("window_buffer"), window);
+    egui::ScrollArea::both()
+        .show(ui, |ui| {
+            let (response, painter) =
+                ui.allocate_painter(Vec2::new(max_width, ui.available_height()), Sense::hover());
+
+            let layout = board_layout(response.rect.left_top(), size, window);
+            let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+            painter.image(texture, layout.rect, uv, Color32::WHITE);
+            layout
+        })
+        .inner
+}
+
+fn board_layout(origin: Pos2, cell_size: f32, window: &WindowBuffer) -> BoardLayout {
+    BoardLayout {
+        origin,
+        cell_size,
+        rect: Rect::from_min_size(
+            origin,
+            Vec2::new(window.width() as f32, window.height() as f32) * cell_size,
+        ),
+    }
+}
+
+/// The texture showing `window`, cached in the egui memory under `id` with
+/// the hash of the buffer it was made from.
+fn buffer_texture(ctx: &egui::Context, id: egui::Id, window: &WindowBuffer) -> TextureId {
+    let hash = buffer_hash(window);
+    let cached: Option<(u64, TextureHandle)> = ctx.data(|d| d.get_temp(id));
+    match cached {
+        Some((uploaded, texture)) if uploaded == hash => texture.id(),
+        Some((_, mut texture)) => {
+            texture.set(color_image(window), TextureOptions::NEAREST);
+            ctx.data_mut(|d| d.insert_temp(id, (hash, texture.clone())));
+            texture.id()
+        }
+        None => {
+            let texture = ctx.load_texture(
+                "window_buffer",
+                color_image(window),
+                TextureOptions::NEAREST,
+            );
+            ctx.data_mut(|d| d.insert_temp(id, (hash, texture.clone())));
+            texture.id()
+        }
+    }
+}
+
+fn buffer_hash(window: &WindowBuffer) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (window.width(), window.height()).hash(&mut hasher);
+    for y in 0..window.height() {
+        for x in 0..window.width() {
+            window[(x, y)].hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
 
-        let base_position = response.rect.left_top().to_vec2();
+fn color_image(window: &WindowBuffer) -> ColorImage {
+    let mut pixels = Vec::with_capacity(window.width() * window.height());
+    for y in 0..window.height() {
         for x in 0..window.width() {
-            for y in 0..window.height() {
-                let color = window[(x, y)];
-
-                let rect = egui::Rect {
-                    min: Pos2 {
-                        x: x as f32 * size,
-                        y: y as f32 * size,
-                    },
-                    max: Pos2 {
-                        x: (x + 1) as f32 * size,
-                        y: (y + 1) as f32 * size,
-                    },
-                };
-
-                let rect = rect.translate(base_position);
-
-                let [r, g, b, a] = color.to_ne_bytes();
-                painter.rect_filled(
-                    rect,
-                    Rounding::ZERO,
-                    Color32::from_rgba_premultiplied(r, g, b, a),
-                );
+            let [r, g, b, a] = window[(x, y)].to_ne_bytes();
+            pixels.push(Color32::from_rgba_premultiplied(r, g, b, a));
+        }
+    }
+    ColorImage {
+        size: [window.width(), window.height()],
+        pixels,
+    }
+}
+
+/// Draws `window` with one rectangle per cell, the slower path kept to compare
+/// with [`draw_window_buffer`].
+pub fn draw_window_buffer_rects(ui: &mut egui::Ui, window: &WindowBuffer) -> BoardLayout {
+    let max_width = ui.available_width();
+    let max_height = ui.available_height();
+    let size = (max_width / window.width() as f32).min(max_height / window.height() as f32);
+    egui::ScrollArea::both()
+        .show(ui, |ui| {
+            let (response, painter) =
+                ui.allocate_painter(Vec2::new(max_width, ui.available_height()), Sense::hover());
+
+            let base_position = response.rect.left_top().to_vec2();
+            for x in 0..window.width() {
+                for y in 0..window.height() {
+                    let color = window[(x, y)];
+
+                    let rect = egui::Rect {
+                        min: Pos2 {
+                            x: x as f32 * size,
+                            y: y as f32 * size,
+                        },
+                        max: Pos2 {
+                            x: (x + 1) as f32 * size,
+                            y: (y + 1) as f32 * size,
+                        },
+                    };
+
+                    let rect = rect.translate(base_position);
+
+                    let [r, g, b, a] = color.to_ne_bytes();
+                    painter.rect_filled(
+                        rect,
+                        Rounding::ZERO,
+                        Color32::from_rgba_premultiplied(r, g, b, a),
+                    );
+                }
             }
+            board_layout(response.rect.left_top(), size, window)
+        })
+        .inner
+}
+
+/// Source of the current time of the games, so their timing can be driven by hand.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// The wall clock, used by the app.
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when [`ManualClock::advance`] is called. Clones
+/// share the same time, keep one to move the clock given to a game.
+#[derive(Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// Runs game ticks of a fixed duration, so games play at the same speed
+/// whatever the frame rate.
+pub struct Scheduler {
+    step: Duration,
+    /// Time elapsed and not yet consumed by a tick.
+    accumulator: Duration,
+    last: Instant,
+    paused: bool,
+    clock: Rc<dyn Clock>,
+}
+
+impl Scheduler {
+    /// Most ticks run in one frame, the time beyond is dropped instead of caught up.
+    pub const MAX_TICKS: u32 = 5;
+
+    pub fn new(step: Duration) -> Self {
+        Self::with_clock(step, Rc::new(RealClock))
+    }
+
+    pub fn with_clock(step: Duration, clock: Rc<dyn Clock>) -> Self {
+        let mut scheduler = Self {
+            step: Duration::ZERO,
+            accumulator: Duration::ZERO,
+            last: clock.now(),
+            paused: false,
+            clock,
+        };
+        scheduler.set_step(step);
+        scheduler
+    }
+
+    /// Replaces the clock, the time elapsed on the previous one is dropped.
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.last = clock.now();
+        self.accumulator = Duration::ZERO;
+        self.clock = clock;
+    }
+
+    /// The clock of the scheduler, to keep it when the game is rebuilt.
+    pub fn clock(&self) -> Rc<dyn Clock> {
+        Rc::clone(&self.clock)
+    }
+
+    /// The current time of the clock of the scheduler.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Changes the tick duration, at least one millisecond.
+    pub fn set_step(&mut self, step: Duration) {
+        self.step = step.max(Duration::from_millis(1));
+    }
+
+    /// The number of ticks due since the last call.
+    pub fn due_ticks(&mut self) -> u32 {
+        if self.paused {
+            return 0;
         }
-    });
+        let now = self.clock.now();
+        self.accumulator += now - self.last;
+        self.last = now;
+
+        let mut ticks = 0;
+        while self.accumulator >= self.step && ticks < Self::MAX_TICKS {
+            self.accumulator -= self.step;
+            ticks += 1;
+        }
+        if ticks == Self::MAX_TICKS {
+            self.accumulator = Duration::ZERO;
+        }
+        ticks
+    }
+
+    /// How long until the next tick is due, to repaint only then.
+    pub fn until_next_tick(&self) -> Duration {
+        self.step.saturating_sub(self.accumulator)
+    }
+
+    /// Stops counting time until [`Scheduler::resume`].
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.last = self.clock.now();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
 }
 
 pub fn colour_changer(rgba_colour_to_change: u32, ui: &mut Ui) -> u32 {
@@ -190,3 +468,162 @@ pub fn colour_changer(rgba_colour_to_change: u32, ui: &mut Ui) -> u32 {
     let convert_color = Rgba::to_srgba_unmultiplied(&colour_player);
     u32::from_le_bytes(convert_color)
 }
+
+/// Writes `bytes` to `path` natively, and downloads them as a file named `path` on the web.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(path: &str, _mime: &str, bytes: &[u8]) -> Result<(), String> {
+    std::fs::write(path, bytes).map_err(|e| format!("{path}: {e}"))
+}
+
+/// Writes `bytes` to `path` natively, and downloads them as a file named `path` on the web.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(path: &str, mime: &str, bytes: &[u8]) -> Result<(), String> {
+    use wasm_bindgen::JsCast;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("{e:?}"))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(|e| format!("{e:?}"))?;
+
+    let anchor = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.create_element("a").ok())
+        .and_then(|a| a.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+        .ok_or("Could not create a download link")?;
+    anchor.set_href(&url);
+    anchor.set_download(path);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(|e| format!("{e:?}"))
+}
+
+/// The fragment of the page URL, with its leading `#`.
+#[cfg(target_arch = "wasm32")]
+pub fn location_hash() -> String {
+    web_sys::window()
+        .and_then(|w| w.location().hash().ok())
+        .unwrap_or_default()
+}
+
+/// Changes the fragment of the page URL, adding an entry to the browser history.
+#[cfg(target_arch = "wasm32")]
+pub fn set_location_hash(hash: &str) {
+    if let Some(window) = web_sys::window() {
+        if let Err(e) = window.location().set_hash(hash) {
+            log::warn!("Could not set the URL hash: {e:?}");
+        }
+    }
+}
+
+/// Repaints `ctx` when the fragment of the page URL changes, by a link or the
+/// back and forward buttons, so the app can follow it.
+#[cfg(target_arch = "wasm32")]
+pub fn repaint_on_hash_change(ctx: &egui::Context) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let ctx = ctx.clone();
+    let on_change = Closure::<dyn Fn()>::new(move || ctx.request_repaint());
+    if let Some(window) = web_sys::window() {
+        if let Err(e) = window
+            .add_event_listener_with_callback("hashchange", on_change.as_ref().unchecked_ref())
+        {
+            log::warn!("Could not listen to URL hash changes: {e:?}");
+        }
+    }
+    // The listener lives as long as the page.
+    on_change.forget();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_are_half_open() {
+        let window = WindowBuffer::new(4, 3);
+        let layout = board_layout(Pos2::new(10.0, 20.0), 5.0, &window);
+        assert_eq!(layout.cell_at(Pos2::new(10.0, 20.0)), Some((0, 0)));
+        assert_eq!(layout.cell_at(Pos2::new(14.9, 24.9)), Some((0, 0)));
+        assert_eq!(layout.cell_at(Pos2::new(15.0, 25.0)), Some((1, 1)));
+        assert_eq!(layout.cell_at(Pos2::new(29.9, 34.9)), Some((3, 2)));
+        assert_eq!(layout.cell_at(Pos2::new(30.0, 30.0)), None);
+        assert_eq!(layout.cell_at(Pos2::new(20.0, 35.0)), None);
+        assert_eq!(layout.cell_at(Pos2::new(9.9, 20.0)), None);
+        assert_eq!(layout.cell_at(Pos2::new(10.0, 19.9)), None);
+    }
+
+    #[test]
+    fn textures_are_updated_when_the_buffer_changes() {
+        let ctx = egui::Context::default();
+        let id = egui::Id::new("board");
+        let cached_hash = || {
+            ctx.data(|d| d.get_temp::<(u64, TextureHandle)>(id))
+                .unwrap()
+                .0
+        };
+        let mut window = WindowBuffer::new(4, 3);
+
+        let texture = buffer_texture(&ctx, id, &window);
+        let hash = cached_hash();
+        assert_eq!(buffer_texture(&ctx, id, &window), texture);
+        assert_eq!(cached_hash(), hash);
+
+        window[(2, 1)] = 0xff00ff00;
+        assert_eq!(buffer_texture(&ctx, id, &window), texture);
+        assert_ne!(cached_hash(), hash);
+    }
+
+    fn scheduler(step_ms: u64) -> (Scheduler, ManualClock) {
+        let clock = ManualClock::new();
+        let scheduler =
+            Scheduler::with_clock(Duration::from_millis(step_ms), Rc::new(clock.clone()));
+        (scheduler, clock)
+    }
+
+    #[test]
+    fn ticks_follow_the_clock() {
+        let (mut scheduler, clock) = scheduler(30);
+        assert_eq!(scheduler.due_ticks(), 0);
+        clock.advance(Duration::from_millis(29));
+        assert_eq!(scheduler.due_ticks(), 0);
+        assert_eq!(scheduler.until_next_tick(), Duration::from_millis(1));
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(scheduler.due_ticks(), 1);
+        clock.advance(Duration::from_millis(95));
+        assert_eq!(scheduler.due_ticks(), 3);
+        assert_eq!(scheduler.until_next_tick(), Duration::from_millis(25));
+    }
+
+    #[test]
+    fn late_frames_drop_the_time_beyond_max_ticks() {
+        let (mut scheduler, clock) = scheduler(10);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(scheduler.due_ticks(), Scheduler::MAX_TICKS);
+        assert_eq!(scheduler.due_ticks(), 0);
+    }
+
+    #[test]
+    fn paused_time_is_not_ticked() {
+        let (mut scheduler, clock) = scheduler(10);
+        scheduler.pause();
+        clock.advance(Duration::from_millis(30));
+        assert_eq!(scheduler.due_ticks(), 0);
+        scheduler.resume();
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(scheduler.due_ticks(), 1);
+    }
+
+    #[test]
+    fn a_new_clock_starts_from_its_time() {
+        let (mut scheduler, clock) = scheduler(10);
+        clock.advance(Duration::from_millis(5));
+        let other = ManualClock::new();
+        scheduler.set_clock(Rc::new(other.clone()));
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(scheduler.due_ticks(), 0);
+        other.advance(Duration::from_millis(10));
+        assert_eq!(scheduler.due_ticks(), 1);
+    }
+}
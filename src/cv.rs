@@ -1,199 +1,479 @@
+use std::{collections::BTreeMap, fmt, path::Path};
+
 use egui::RichText;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+
+use crate::json_resume::JsonResume;
+
+pub const EMBEDDED_RESUME: &str = include_str!("../assets/resume.toml");
+
+/// Everything displayed on the resume page.
+///
+/// Free text fields (summaries, descriptions) may contain `**strong**` spans,
+/// which are rendered in bold.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cv {
+    pub identity: Identity,
+    pub summary: Vec<String>,
+    pub experience: Vec<Experience>,
+    pub education: Vec<Education>,
+    pub projects: Vec<Project>,
+    pub skills: Vec<SkillGroup>,
+    pub languages: Vec<Language>,
+    pub links: Vec<Link>,
+    pub labels: Labels,
+    /// Translated content by locale code (`fr`, `ja`...), missing fields fall
+    /// back to the English content above.
+    #[serde(
+        skip_serializing_if = "BTreeMap::is_empty",
+        deserialize_with = "translations"
+    )]
+    pub translations: BTreeMap<String, Cv>,
+}
+
+/// A translation as written in a resume file.
+#[derive(Deserialize)]
+struct Translation {
+    #[serde(default)]
+    labels: TranslatedLabels,
+    #[serde(flatten)]
+    content: Cv,
+}
+
+/// The labels of a translation: those it leaves out are kept empty, so they
+/// fall back to the resume's own labels instead of [`Labels::default`].
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct TranslatedLabels {
+    at_a_glance: Option<String>,
+    languages_spoken: Option<String>,
+    social_media: Option<String>,
+    greeting: Option<String>,
+    education: Option<String>,
+    projects: Option<String>,
+    frameworks: Option<String>,
+    summary_section: Option<String>,
+    experience_section: Option<String>,
+    education_section: Option<String>,
+    projects_section: Option<String>,
+    skills_section: Option<String>,
+    position_at: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+}
+
+impl From<TranslatedLabels> for Labels {
+    fn from(labels: TranslatedLabels) -> Self {
+        Labels {
+            at_a_glance: labels.at_a_glance.unwrap_or_default(),
+            languages_spoken: labels.languages_spoken.unwrap_or_default(),
+            social_media: labels.social_media.unwrap_or_default(),
+            greeting: labels.greeting.unwrap_or_default(),
+            education: labels.education.unwrap_or_default(),
+            projects: labels.projects.unwrap_or_default(),
+            frameworks: labels.frameworks.unwrap_or_default(),
+            summary_section: labels.summary_section.unwrap_or_default(),
+            experience_section: labels.experience_section.unwrap_or_default(),
+            education_section: labels.education_section.unwrap_or_default(),
+            projects_section: labels.projects_section.unwrap_or_default(),
+            skills_section: labels.skills_section.unwrap_or_default(),
+            position_at: labels.position_at.unwrap_or_default(),
+            since: labels.since.unwrap_or_default(),
+            until: labels.until.unwrap_or_default(),
+        }
+    }
+}
+
+fn translations<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Cv>, D::Error> {
+    let translations = BTreeMap::<String, Translation>::deserialize(deserializer)?;
+    Ok(translations
+        .into_iter()
+        .map(|(locale, translation)| {
+            let labels = translation.labels.into();
+            (
+                locale,
+                Cv {
+                    labels,
+                    ..translation.content
+                },
+            )
+        })
+        .collect())
+}
+
+/// Fixed texts of the resume page, so they can be translated with the content.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Labels {
+    pub at_a_glance: String,
+    pub languages_spoken: String,
+    pub social_media: String,
+    /// `{name}` is replaced by the name from the identity.
+    pub greeting: String,
+    pub education: String,
+    pub projects: String,
+    pub frameworks: String,
+    /// Section titles of the exported documents.
+    pub summary_section: String,
+    pub experience_section: String,
+    pub education_section: String,
+    pub projects_section: String,
+    pub skills_section: String,
+    /// `{position}` and `{company}` are replaced by those of the experience.
+    pub position_at: String,
+    /// `{date}` is replaced by the start of an ongoing period.
+    pub since: String,
+    /// `{date}` is replaced by the end of a period without a start.
+    pub until: String,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Self {
+            at_a_glance: "At a glance".to_string(),
+            languages_spoken: "Languages spoken".to_string(),
+            social_media: "Social media".to_string(),
+            greeting: "Hello, my name is {name}".to_string(),
+            education: "Concerning my Education:".to_string(),
+            projects: "My notable projects:".to_string(),
+            frameworks: "Framework used".to_string(),
+            summary_section: "Summary".to_string(),
+            experience_section: "Experience".to_string(),
+            education_section: "Education".to_string(),
+            projects_section: "Projects".to_string(),
+            skills_section: "Skills".to_string(),
+            position_at: "{position} at {company}".to_string(),
+            since: "since {date}".to_string(),
+            until: "until {date}".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Identity {
+    pub name: String,
+    pub headline: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Experience {
+    pub company: String,
+    pub position: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub summary: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Education {
+    pub institution: String,
+    pub area: String,
+    pub study_type: String,
+    pub start: Option<String>,
+    pub summary: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Project {
+    pub name: String,
+    pub url: Option<String>,
+    pub description: String,
+    pub keywords: Vec<String>,
+    pub highlights: Vec<Highlight>,
+}
+
+/// A named feature of a project, e.g. "Ghost mode" for the snake.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Highlight {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkillGroup {
+    pub name: String,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Language {
+    pub language: String,
+    pub fluency: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Link {
+    pub network: String,
+    pub url: String,
+}
 
-#[derive(Clone, Default)]
-pub struct Cv {}
+impl Labels {
+    /// The title of `experience`, like "Developer at Company".
+    pub fn position_at(&self, experience: &Experience) -> String {
+        self.position_at
+            .replace("{position}", &experience.position)
+            .replace("{company}", &experience.company)
+    }
+
+    /// The dates of a period, like "2020 - 2022" or "since 2020".
+    pub fn period(&self, start: &Option<String>, end: &Option<String>) -> Option<String> {
+        match (start, end) {
+            (Some(start), Some(end)) => Some(format!("{start} - {end}")),
+            (Some(start), None) => Some(self.since.replace("{date}", start)),
+            (None, Some(end)) => Some(self.until.replace("{date}", end)),
+            (None, None) => None,
+        }
+    }
+}
 
 impl Cv {
-    pub fn cv(ctx: &egui::Context) {
-        egui::CentralPanel::default().show(ctx, |ui| {
+    /// The resume shipped with the app.
+    pub fn embedded() -> Result<Self, LoadError> {
+        Self::from_toml(EMBEDDED_RESUME)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, LoadError> {
+        toml::from_str(content).map_err(|e| LoadError {
+            message: e.message().to_string(),
+            position: e.span().map(|span| line_column(content, span.start)),
+        })
+    }
+
+    /// Parses either our own format or a [JSON Resume](https://jsonresume.org/schema),
+    /// recognized by its `basics` section.
+    pub fn from_json(content: &str) -> Result<Self, LoadError> {
+        let is_json_resume = serde_json::from_str::<serde_json::Value>(content)
+            .is_ok_and(|value| value.get("basics").is_some());
+        if is_json_resume {
+            json_from_str::<JsonResume>(content).map(Cv::from)
+        } else {
+            json_from_str(content)
+        }
+    }
+
+    /// Exports the resume in the [JSON Resume](https://jsonresume.org/schema) schema.
+    pub fn to_json_resume(&self) -> String {
+        serde_json::to_string_pretty(&JsonResume::from(self))
+            .expect("a resume is always serializable")
+    }
+
+    /// Parses `content` as JSON if `name` ends with `.json`, and as TOML otherwise.
+    pub fn parse(name: &str, content: &str) -> Result<Self, LoadError> {
+        if name.ends_with(".json") {
+            Self::from_json(content)
+        } else {
+            Self::from_toml(content)
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, LoadError> {
+        let content = std::fs::read_to_string(path).map_err(|e| LoadError {
+            message: format!("{}: {e}", path.display()),
+            position: None,
+        })?;
+        Self::parse(&path.to_string_lossy(), &content)
+    }
+}
+
+/// Why a resume file couldn't be used, with the 1-based `(line, column)` of
+/// the faulty value when known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadError {
+    pub message: String,
+    pub position: Option<(usize, usize)>,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(f, "line {line}, column {column}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+fn json_from_str<T: DeserializeOwned>(content: &str) -> Result<T, LoadError> {
+    serde_json::from_str(content).map_err(|e| {
+        // serde_json appends the position to its message, we display it ourselves.
+        let message = e.to_string();
+        let position = format!(" at line {} column {}", e.line(), e.column());
+        LoadError {
+            message: message
+                .strip_suffix(&position)
+                .unwrap_or(&message)
+                .to_string(),
+            position: Some((e.line(), e.column())),
+        }
+    })
+}
+
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+/// Splits a text on its `**` markers, yielding `(segment, is_strong)` pairs
+/// with the whitespace around the markers kept.
+pub fn spans(text: &str) -> impl Iterator<Item = (&str, bool)> {
+    text.split("**")
+        .enumerate()
+        .map(|(i, segment)| (segment, i % 2 == 1))
+        .filter(|(segment, _)| !segment.is_empty())
+}
+
+fn rich_label(ui: &mut egui::Ui, text: &str) {
+    // The labels are spaced by the layout.
+    for (segment, strong) in spans(text) {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            continue;
+        }
+        if strong {
+            ui.label(RichText::new(segment).strong());
+        } else {
+            ui.label(segment);
+        }
+    }
+}
 
+impl Cv {
+    pub fn cv(&self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
             egui::SidePanel::left("left_panel")
-            .resizable(true)
-            .default_width(500.0)
-            .width_range(80.0..=500.0)
-            .show_inside(ui, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.heading("At a glance");
+                .resizable(true)
+                .default_width(500.0)
+                .width_range(80.0..=500.0)
+                .show_inside(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.heading(&self.labels.at_a_glance);
+                    });
+                    egui::ScrollArea::vertical().show(ui, |ui| self.at_a_glance(ui));
                 });
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.label("\n");
-                    ui.label(RichText::new("LANGUAGES SPOKEN:").strong());
-                    ui.label("French (native)");
-                    ui.label("Italian (native)");
-                    ui.label("English (bilingual)");
-                    ui.label("Japanese (intermadiary)");
-
-                    ui.label("\n");
-                    ui.separator();
-
-                    ui.label(RichText::new("PROGRAMMING LANGUAGES:").strong());
-                    ui.label("Rust");
-                    ui.label("SQL");
-                    ui.label("HTML");
-                    ui.label("CSS");
-                    ui.label("Python");
-
-                    ui.label("\n");
-                    ui.separator();
-
-                    ui.label(RichText::new("FRAMEWORKS:").strong());
-                    ui.label("Actix-web");
-                    ui.label("Reqwest");
-                    ui.label("Clap");
-                    ui.label("Tokio");
-                    ui.label("Sqlx");
-                    ui.label("Axum");
-
-                    ui.label("\n");
-                    ui.separator();
-
-                    ui.label(RichText::new("TOOLS:").strong());
-                    ui.label("Git");
-                    ui.label("GitHub");
-                    ui.label("PosgreSQL");
-                    ui.label("Docker");
-                    ui.label("Bash");
-                    ui.label("Curl");
-
-                    ui.label("\n");
-                    ui.separator();
-
-                    ui.label(RichText::new("SOCIAL MEDIA:").strong());
-                    ui.hyperlink_to("LinkedIn", "https://www.linkedin.com/in/luna-ferraraccio-01553a110/");
-                    ui.hyperlink_to("GitHub", "https://github.com/NoodleSamaChan");
-                });
-            });
 
             ui.vertical_centered(|ui| {
                 ui.set_max_width(1100.);
 
                 ui.horizontal_wrapped(|ui| {
                     // Trick so we don't have to add spaces in the text below:
-                    let width = ui.fonts(|f|f.glyph_width(&egui::TextStyle::Body.resolve(ui.style()), ' '));
+                    let width = ui
+                        .fonts(|f| f.glyph_width(&egui::TextStyle::Body.resolve(ui.style()), ' '));
                     ui.spacing_mut().item_spacing.x = width;
-                    
-                    ui.separator();
-                    ui.add_space(20.);
-                    ui.heading("Hello, my name is Luna Ferraraccio");
-                    ui.label("\n");
-                    ui.label("Junior Back-end developer seeking new opportunities. Before becoming a Rust back-end developer, I acutally used to work in communication.");
-                    ui.label("\n");
-                    ui.label("I worked for ");
-                    ui.label(RichText::new("6 years at Ubisoft,").strong());
-                    ui.label("the video game developer, in various community and communication roles.");
-                    ui.label("\n");
-                    ui.label("I was a");
-                    ui.label(RichText::new("Community Manager, a Listening Coordinator, and finally the team lead of the APAC community team.").strong());
-                    ui.label("\n");
-                    ui.label("I discovered the world of developement and coding during my work at ");
-                    ui.label(RichText::new("Meilisearch as a Lead Community Developer.").strong());
-                    ui.label("\n");
-                    ui.label("I fell in love with coding and decided to start a new adventure in my professional life.\n");
-                    ui.label("\n");
-                    ui.label("\n");
-                    ui.separator();
-
-                    ui.add_space(20.);
-                    ui.heading("Concerning my Education:");
-                    ui.label("\n");
-                    ui.label("\n");
-                    ui.label("I studied");
-                    ui.label(RichText::new("English language, litterature, and history").strong());
-                    ui.label("at the university of Le Mans, France, for my Bachelor.");
-                    ui.label("\n");
-                    ui.label("Then, I also studied");
-                    ui.label(RichText::new("Culture, Communication, and globalization (with a minor in gender studies)").strong());
-                    ui.label("at the university of Aalborg, Denmark, for my Masters.");
-                    ui.label("\n");
-                    ui.label("As part of my continued evolution at Ubisoft, I also benefited from a formal");
-                    ui.label(RichText::new("Project Management training, as well as a People Management training").strong());
-                    ui.label("\n");
-                    ui.label("Finally, I started learning coding");
-                    ui.label(RichText::new("from July 2023").strong());
-                    ui.label("During that time, I've tackled number of projects in order learn as much as I could about coding, and Rust in particular.\n");
-                    ui.label("\n");
-                    ui.label("\n");
-                    ui.separator();
-                    
-                    ui.add_space(20.);
-                    ui.heading("My notable projects:");
-                    ui.label("\n");
-                    ui.label("\n");
-                    ui.hyperlink_to("LairBnB -> NEED TO REDO THE PROJECT IN RUST", "https://meilisearch.com");
-                    ui.label("\n");
-                    ui.label("My very own version of the famous AirBnB website. In this project, I learned how to manage databases and a REST API.");
-                    ui.label("\n");
-                    ui.label(RichText::new("Framework used: Tokio, Reqwest, Actix-web, Sqlx").strong());
-                    ui.label("\n");
-                    ui.label("\n");
-
-                    ui.label("Newsletter");
-                    ui.hyperlink_to(" Zero To Production (L. Palmieri)", "https://www.zero2prod.com/index.html");
-                    ui.label("\n");
-                    ui.label("Palmieri is well known for his educational work in the Rust community and allowed me to create my own Newsletter program for my own website and learn a lot about coding best practices. ");
-                    ui.label("\n");
-                    ui.label(RichText::new("Framework used: Tokio, Reqwest, Actix-web, Sqlx").strong());
-                    ui.label("\n");
-                    ui.label("\n");
-
-                    ui.label(RichText::new("Game projects:").strong());
-                    ui.label("\n");
-                    ui.label("I have produced a series of projects around well known, old style games which allowed me to get much more familiar with programming logic, the language of Rust, project management, code management. You'll be able to try all of these games on this website. Please feel free to try them and have fun. :) I'll link the GitHub repos below if you're curious.");
-                    ui.label("\n");
-                    ui.label("\n");
-
-                    ui.hyperlink_to(" Snake", "https://github.com/NoodleSamaChan/snake");
-                    ui.label("\n");
-                    ui.label("I've implemented number of functionalities with the Snake project. There's a classic mode of course, but you can setup a number of options should you wish to.");
-                    ui.label("\n");
-                    ui.label(RichText::new("Ghost mode: ").strong());
-                    ui.label("will allow your snake to safely go through walls");
-                    ui.label("\n");
-                    ui.label(RichText::new("Two players mode").strong());
-                    ui.label("will create a second snake, if you wish to share a game locally with someone else.");
-                    ui.label("\n");
-                    ui.label(RichText::new("Bad berry mode: ").strong());
-                    ui.label("will generate a 'bad berry' that, if eaten, give you a disadvantage that will only be cancelled by eating a second bad berry.");
-                    ui.label("\n");
-                    ui.label(RichText::new("Change the colour: ").strong());
-                    ui.label("personalise the colours of whichever element of the game you want");
-                    ui.label("\n");
-                    ui.label(RichText::new("Snake size: ").strong());
-                    ui.label("personalise the size of your snake at the start of the game");
-                    ui.label("\n");
-                    ui.label(RichText::new("Snake speed: ").strong());
-                    ui.label("set the speed of your snake to make the game more or less challenging.");
-                    ui.label("\n");
-                    ui.label("\n");
-
-                    ui.hyperlink_to("Pong", "https://github.com/NoodleSamaChan/pong");
-                    ui.label("\n");
-                    ui.label(RichText::new("Speed of the pongs and the ball: ").strong());
-                    ui.label("set the respective speeds of the pongs and the ball to make the game more or less challenging.");
-                    ui.label("\n");
-                    ui.label(RichText::new("Change the colour: ").strong());
-                    ui.label("personalise the colours of whichever element of the game you want");
-                    ui.label("\n");
-                    ui.label("\n");
-
-                    ui.hyperlink_to("Maze", "https://github.com/NoodleSamaChan/naze");
-                    ui.label("\n");
-                    ui.label("Have fun finding your way out of the maze!");
-                    ui.label("\n");
-                    ui.label(RichText::new("Change the colour: ").strong());
-                    ui.label("personalise the colours of whichever element of the game you want");
-                    ui.label("\n");
-                    ui.label("\n");
-
-                    ui.hyperlink_to("Game of Life", "https://github.com/NoodleSamaChan/rust_project/tree/main/game_of_life");
-                    ui.label("\n");
-                    ui.label("The famous game of life");
-                    ui.label("\n");
-                    ui.label(RichText::new("Change the colour: ").strong());
-                    ui.label("personalise the colours of whichever element of the game you want");
 
+                    self.narrative(ui);
                 });
             });
         });
     }
+
+    fn at_a_glance(&self, ui: &mut egui::Ui) {
+        ui.label("\n");
+        ui.label(
+            RichText::new(format!("{}:", self.labels.languages_spoken.to_uppercase())).strong(),
+        );
+        for language in &self.languages {
+            ui.label(format!("{} ({})", language.language, language.fluency));
+        }
+
+        for group in &self.skills {
+            ui.label("\n");
+            ui.separator();
+
+            ui.label(RichText::new(format!("{}:", group.name.to_uppercase())).strong());
+            for keyword in &group.keywords {
+                ui.label(keyword);
+            }
+        }
+
+        ui.label("\n");
+        ui.separator();
+
+        ui.label(RichText::new(format!("{}:", self.labels.social_media.to_uppercase())).strong());
+        for link in &self.links {
+            ui.hyperlink_to(&link.network, &link.url);
+        }
+    }
+
+    fn narrative(&self, ui: &mut egui::Ui) {
+        ui.separator();
+        ui.add_space(20.);
+        ui.heading(self.labels.greeting.replace("{name}", &self.identity.name));
+        ui.label("\n");
+        ui.label(&self.identity.headline);
+        if let Some(first) = self.summary.first() {
+            rich_label(ui, first);
+        }
+        for experience in &self.experience {
+            ui.label("\n");
+            rich_label(ui, &experience.summary);
+        }
+        for paragraph in self.summary.iter().skip(1) {
+            ui.label("\n");
+            rich_label(ui, paragraph);
+        }
+        ui.label("\n");
+        ui.label("\n");
+        ui.separator();
+
+        ui.add_space(20.);
+        ui.heading(&self.labels.education);
+        ui.label("\n");
+        for education in &self.education {
+            ui.label("\n");
+            rich_label(ui, &education.summary);
+        }
+        ui.label("\n");
+        ui.label("\n");
+        ui.separator();
+
+        ui.add_space(20.);
+        ui.heading(&self.labels.projects);
+        ui.label("\n");
+        for project in &self.projects {
+            ui.label("\n");
+            match &project.url {
+                Some(url) => {
+                    ui.hyperlink_to(&project.name, url);
+                }
+                None => {
+                    ui.label(RichText::new(format!("{}:", project.name)).strong());
+                }
+            }
+            if !project.description.is_empty() {
+                ui.label("\n");
+                rich_label(ui, &project.description);
+            }
+            if !project.keywords.is_empty() {
+                ui.label("\n");
+                ui.label(
+                    RichText::new(format!(
+                        "{}: {}",
+                        self.labels.frameworks,
+                        project.keywords.join(", ")
+                    ))
+                    .strong(),
+                );
+            }
+            for highlight in &project.highlights {
+                ui.label("\n");
+                if !highlight.name.is_empty() {
+                    ui.label(RichText::new(format!("{}: ", highlight.name)).strong());
+                }
+                rich_label(ui, &highlight.description);
+            }
+            ui.label("\n");
+        }
+    }
 }
@@ -0,0 +1,70 @@
+//! Fonts installed on top of egui's defaults, which have no CJK glyphs.
+//!
+//! With the `cjk_font` feature, `assets/fonts/NotoSansJP-Subset.otf` is embedded
+//! and used as the last fallback of every family. It only holds the characters
+//! of `assets/resume.toml`: run `./subset_font.sh` to regenerate it after
+//! editing the Japanese content.
+
+/// Name of the CJK font in [`egui::FontDefinitions::font_data`].
+#[cfg(feature = "cjk_font")]
+pub const CJK_FONT: &str = "NotoSansJP-Subset";
+
+/// Adds the bundled fonts to `ctx`, keeping egui's default ones first.
+pub fn install_fonts(ctx: &egui::Context) {
+    #[cfg(feature = "cjk_font")]
+    {
+        let mut fonts = egui::FontDefinitions::default();
+        fonts.font_data.insert(
+            CJK_FONT.to_string(),
+            egui::FontData::from_static(include_bytes!("../assets/fonts/NotoSansJP-Subset.otf")),
+        );
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            fonts
+                .families
+                .entry(family)
+                .or_default()
+                .push(CJK_FONT.to_string());
+        }
+        ctx.set_fonts(fonts);
+    }
+    #[cfg(not(feature = "cjk_font"))]
+    let _ = ctx;
+}
+
+/// Characters of `text` that none of the fonts of `family` can display.
+///
+/// Fonts are only loaded once a frame has started, call this from `update`.
+pub fn missing_glyphs(ctx: &egui::Context, family: egui::FontFamily, text: &str) -> Vec<char> {
+    let font_id = egui::FontId::new(14.0, family);
+    let mut missing: Vec<char> = ctx.fonts(|fonts| {
+        text.chars()
+            .filter(|c| !c.is_whitespace() && !fonts.has_glyph(&font_id, *c))
+            .collect()
+    });
+    missing.sort_unstable();
+    missing.dedup();
+    missing
+}
+
+#[cfg(all(test, feature = "cjk_font"))]
+mod tests {
+    use super::*;
+    use crate::cv::Cv;
+
+    #[test]
+    fn every_locale_is_displayable() {
+        let ctx = egui::Context::default();
+        install_fonts(&ctx);
+        // The fonts set above are only loaded by the next frame.
+        let _ = ctx.run(egui::RawInput::default(), |_| {});
+
+        let cv = Cv::embedded().unwrap();
+        for locale in cv.locales() {
+            let text = cv.localized(locale).to_text(0);
+            for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+                let missing = missing_glyphs(&ctx, family.clone(), &text);
+                assert!(missing.is_empty(), "{locale} {family:?}: {missing:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use egui::{Align2, Color32, FontId, Key, Rect, Sense, Ui};
+use std::rc::Rc;
+use window_rs::WindowBuffer;
+
+use crate::{
+    common::{BoardLayout, Clock, FrameInput, GameInput, Scheduler},
+    draw_window_buffer,
+    keymap::{ActionFrame, Keymap},
+    life::Life,
+    maze::Maze,
+    pong::Pong,
+    snake::Snake,
+};
+
+/// A game playable from the menu bar, drawn from a [`WindowBuffer`] with its
+/// settings in a side panel.
+pub trait Game {
+    /// Identifies the game in the URL hash and the app storage.
+    fn id(&self) -> &'static str;
+
+    /// Label of the game in the menu bar.
+    fn name(&self) -> &'static str;
+
+    fn handle_input(&mut self, input: &dyn GameInput);
+
+    /// Advances the game by one step of its [`Scheduler`].
+    fn tick(&mut self);
+
+    fn scheduler(&self) -> &Scheduler;
+
+    fn scheduler_mut(&mut self) -> &mut Scheduler;
+
+    /// Times the game with `clock` instead of the wall clock.
+    fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.scheduler_mut().set_clock(clock);
+    }
+
+    fn configuration(&mut self, ui: &mut Ui) -> egui::Response;
+
+    fn buffer(&self) -> &WindowBuffer;
+
+    /// Shows the state of the current game above the board.
+    fn hud(&self, _ui: &mut Ui) {}
+
+    /// Starts over with the default settings.
+    fn reset(&mut self);
+
+    /// Freezes the timers of the game, called when its view is hidden.
+    fn pause(&mut self) {
+        self.scheduler_mut().pause();
+    }
+
+    /// Restarts the timers frozen by [`Game::pause`] from where they stopped.
+    fn resume(&mut self) {
+        self.scheduler_mut().resume();
+    }
+
+    fn is_paused(&self) -> bool {
+        self.scheduler().is_paused()
+    }
+
+    /// Restores the settings saved by [`Game::save`].
+    fn load(&mut self, storage: &dyn eframe::Storage);
+
+    fn save(&self, storage: &mut dyn eframe::Storage);
+
+    /// Plays the game, or shows it paused until the board is clicked or Space is pressed.
+    fn ui(&mut self, ctx: &egui::Context, keymap: &Keymap, actions: &ActionFrame) {
+        if !self.is_paused() {
+            // Clicks are mapped on the board as drawn in the previous frame.
+            let layout = BoardLayout::last(ctx);
+            ctx.input(|input| {
+                self.handle_input(&FrameInput {
+                    input,
+                    keymap,
+                    actions,
+                    layout,
+                })
+            });
+            for _ in 0..self.scheduler_mut().due_ticks() {
+                self.tick();
+            }
+            ctx.request_repaint_after(self.scheduler().until_next_tick());
+        }
+
+        egui::SidePanel::right("Configuration").show(ctx, |ui| self.configuration(ui));
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.hud(ui);
+            let layout = draw_window_buffer(ui, self.buffer());
+            layout.store(ctx);
+            let board = layout.rect;
+            if self.is_paused() {
+                let clicked = ui
+                    .interact(board, ui.id().with("paused"), Sense::click())
+                    .clicked();
+                if clicked || ui.input(|i| i.key_pressed(Key::Space)) {
+                    self.resume();
+                    ctx.request_repaint();
+                } else {
+                    paused_overlay(ui, board);
+                }
+            }
+        });
+    }
+}
+
+/// Every game of the menu bar, in order.
+pub fn registry() -> Vec<Box<dyn Game>> {
+    vec![
+        Box::new(Snake::default()),
+        Box::new(Pong::default()),
+        Box::new(Maze::default()),
+        Box::new(Life::default()),
+    ]
+}
+
+fn paused_overlay(ui: &Ui, board: Rect) {
+    let painter = ui.painter_at(board);
+    painter.rect_filled(board, 0.0, Color32::from_black_alpha(160));
+    painter.text(
+        board.center(),
+        Align2::CENTER_BOTTOM,
+        "Paused",
+        FontId::proportional(48.0),
+        Color32::WHITE,
+    );
+    painter.text(
+        board.center(),
+        Align2::CENTER_TOP,
+        "Click or press Space to resume",
+        FontId::proportional(16.0),
+        Color32::WHITE,
+    );
+}
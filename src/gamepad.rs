@@ -0,0 +1,342 @@
+//! Game controllers triggering the same [`graphic::Key`] actions as the
+//! keyboard. Controllers are read from a [`GamepadSource`]: the real ones with
+//! the native-only `gamepad` feature, or [`FakeGamepads`] driven by hand.
+
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::keymap::{index, ActionFrame, ACTIONS};
+
+/// A button, or a direction of the left stick, of a controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    StickUp,
+    StickDown,
+    StickLeft,
+    StickRight,
+    /// The bottom face button, A on Xbox controllers.
+    South,
+    /// The right face button, B on Xbox controllers.
+    East,
+    /// The top face button, Y on Xbox controllers.
+    North,
+    /// The left face button, X on Xbox controllers.
+    West,
+    LeftTrigger,
+    RightTrigger,
+    Start,
+    Select,
+}
+
+/// The player a controller plays as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    pub fn name(self) -> &'static str {
+        match self {
+            Player::One => "Player 1",
+            Player::Two => "Player 2",
+        }
+    }
+
+    /// The actions `control` triggers for this player.
+    pub(crate) fn actions(self, control: Control) -> &'static [graphic::Key] {
+        use graphic::Key::*;
+        match (self, control) {
+            (Player::One, Control::DPadUp | Control::StickUp) => &[Up, UpPlayer1],
+            (Player::One, Control::DPadDown | Control::StickDown) => &[Down, DownPlayer1],
+            (Player::One, Control::DPadLeft | Control::StickLeft) => &[Left],
+            (Player::One, Control::DPadRight | Control::StickRight) => &[Right],
+            (Player::Two, Control::DPadUp | Control::StickUp) => &[UpPlayer2],
+            (Player::Two, Control::DPadDown | Control::StickDown) => &[DownPlayer2],
+            (Player::Two, Control::DPadLeft | Control::StickLeft) => &[LeftPlayer2],
+            (Player::Two, Control::DPadRight | Control::StickRight) => &[RightPlayer2],
+            (_, Control::South) => &[Space, Launch],
+            (_, Control::East) => &[Escape],
+            (_, Control::RightTrigger) => &[Forward],
+            (_, Control::LeftTrigger) => &[Backward],
+            (_, Control::Start) => &[Launch],
+            (_, Control::North | Control::West | Control::Select) => &[],
+        }
+    }
+}
+
+/// A connected controller and the controls held on it.
+#[derive(Clone, Debug, Default)]
+pub struct Controller {
+    pub name: String,
+    pub held: Vec<Control>,
+}
+
+/// Where the controllers are read from, once per frame.
+pub trait GamepadSource {
+    /// Every connected controller, in the order they were connected.
+    fn poll(&mut self) -> Vec<Controller>;
+}
+
+/// Controllers whose controls are set by hand, to check the mappings
+/// without a controller plugged in. Clones share the same controllers, keep
+/// one to change those read by [`Gamepads`].
+#[derive(Clone, Debug, Default)]
+pub struct FakeGamepads {
+    controllers: Rc<RefCell<Vec<Controller>>>,
+}
+
+impl FakeGamepads {
+    /// Connects `controllers`, read from the next [`Gamepads::update`] on.
+    pub fn set(&self, controllers: Vec<Controller>) {
+        *self.controllers.borrow_mut() = controllers;
+    }
+}
+
+impl GamepadSource for FakeGamepads {
+    fn poll(&mut self) -> Vec<Controller> {
+        self.controllers.borrow().clone()
+    }
+}
+
+/// The controllers of the computer, read with gilrs.
+#[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+pub struct GilrsGamepads {
+    gilrs: gilrs::Gilrs,
+}
+
+#[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+impl GilrsGamepads {
+    pub fn new() -> Result<Self, String> {
+        let gilrs = gilrs::Gilrs::new().map_err(|e| e.to_string())?;
+        Ok(Self { gilrs })
+    }
+}
+
+#[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+impl GamepadSource for GilrsGamepads {
+    fn poll(&mut self) -> Vec<Controller> {
+        use gilrs::{Axis, Button};
+
+        /// Stick deflection past which it counts as a direction.
+        const DEAD_ZONE: f32 = 0.5;
+        const BUTTONS: [(Button, Control); 12] = [
+            (Button::DPadUp, Control::DPadUp),
+            (Button::DPadDown, Control::DPadDown),
+            (Button::DPadLeft, Control::DPadLeft),
+            (Button::DPadRight, Control::DPadRight),
+            (Button::South, Control::South),
+            (Button::East, Control::East),
+            (Button::North, Control::North),
+            (Button::West, Control::West),
+            (Button::LeftTrigger, Control::LeftTrigger),
+            (Button::RightTrigger, Control::RightTrigger),
+            (Button::Start, Control::Start),
+            (Button::Select, Control::Select),
+        ];
+
+        // The state of the gamepads is only updated as their events are read.
+        while self.gilrs.next_event().is_some() {}
+
+        self.gilrs
+            .gamepads()
+            .map(|(_, gamepad)| {
+                let mut held: Vec<Control> = BUTTONS
+                    .iter()
+                    .filter(|(button, _)| gamepad.is_pressed(*button))
+                    .map(|(_, control)| *control)
+                    .collect();
+                let x = gamepad.value(Axis::LeftStickX);
+                let y = gamepad.value(Axis::LeftStickY);
+                if y > DEAD_ZONE {
+                    held.push(Control::StickUp);
+                }
+                if y < -DEAD_ZONE {
+                    held.push(Control::StickDown);
+                }
+                if x < -DEAD_ZONE {
+                    held.push(Control::StickLeft);
+                }
+                if x > DEAD_ZONE {
+                    held.push(Control::StickRight);
+                }
+                Controller {
+                    name: gamepad.name().to_string(),
+                    held,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Turns the controls held on the controllers into game actions, each
+/// controller playing as the player it is assigned to.
+pub struct Gamepads {
+    source: Box<dyn GamepadSource>,
+    controllers: Vec<Controller>,
+    /// The player of each controller, by connection order. Controllers past
+    /// the end of the list alternate between player 1 and player 2.
+    pub players: Vec<Player>,
+    frame: ActionFrame,
+}
+
+impl Gamepads {
+    pub fn new(source: impl GamepadSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            controllers: Vec::new(),
+            players: Vec::new(),
+            frame: ActionFrame::default(),
+        }
+    }
+
+    /// The controllers of the computer, when the `gamepad` feature is enabled
+    /// and they can be read.
+    pub fn native() -> Option<Self> {
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        match GilrsGamepads::new() {
+            Ok(source) => return Some(Self::new(source)),
+            Err(e) => log::warn!("Could not read the game controllers: {e}"),
+        }
+        None
+    }
+
+    pub fn player(&self, controller: usize) -> Player {
+        match self.players.get(controller) {
+            Some(player) => *player,
+            None if controller % 2 == 0 => Player::One,
+            None => Player::Two,
+        }
+    }
+
+    /// Reads the controllers, to be called once per frame.
+    pub fn update(&mut self) -> &ActionFrame {
+        self.controllers = self.source.poll();
+        let mut down = [false; ACTIONS.len()];
+        for (i, controller) in self.controllers.iter().enumerate() {
+            let player = self.player(i);
+            for control in &controller.held {
+                for action in player.actions(*control) {
+                    down[index(action)] = true;
+                }
+            }
+        }
+        self.frame = self.frame.next(down);
+        &self.frame
+    }
+
+    pub fn frame(&self) -> &ActionFrame {
+        &self.frame
+    }
+
+    /// Lets each connected controller be assigned to a player.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if self.controllers.is_empty() {
+            ui.label("No controller connected.");
+            return;
+        }
+        egui::Grid::new("controllers").show(ui, |ui| {
+            for i in 0..self.controllers.len() {
+                let mut player = self.player(i);
+                ui.label(&self.controllers[i].name);
+                egui::ComboBox::from_id_source(("controller", i))
+                    .selected_text(player.name())
+                    .show_ui(ui, |ui| {
+                        for choice in [Player::One, Player::Two] {
+                            ui.selectable_value(&mut player, choice, choice.name());
+                        }
+                    });
+                if player != self.player(i) {
+                    while self.players.len() <= i {
+                        self.players.push(self.player(self.players.len()));
+                    }
+                    self.players[i] = player;
+                }
+                ui.end_row();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphic::Key;
+
+    fn controller(held: &[Control]) -> Controller {
+        Controller {
+            name: "pad".to_string(),
+            held: held.to_vec(),
+        }
+    }
+
+    fn gamepads() -> (Gamepads, FakeGamepads) {
+        let fake = FakeGamepads::default();
+        (Gamepads::new(fake.clone()), fake)
+    }
+
+    #[test]
+    fn unassigned_controllers_alternate_players() {
+        let (mut gamepads, fake) = gamepads();
+        fake.set(vec![
+            controller(&[]),
+            controller(&[Control::DPadLeft]),
+            controller(&[Control::DPadUp]),
+        ]);
+        let frame = gamepads.update();
+        assert!(frame.is_down(&Key::LeftPlayer2));
+        assert!(frame.is_down(&Key::Up));
+        assert!(!frame.is_down(&Key::Left));
+        assert!(!frame.is_down(&Key::UpPlayer2));
+        assert_eq!(gamepads.player(3), Player::Two);
+    }
+
+    #[test]
+    fn assigned_controllers_play_as_their_player() {
+        let (mut gamepads, fake) = gamepads();
+        gamepads.players = vec![Player::Two, Player::Two];
+        fake.set(vec![
+            controller(&[Control::StickDown]),
+            controller(&[Control::DPadRight]),
+            controller(&[Control::DPadUp]),
+        ]);
+        let frame = gamepads.update();
+        assert!(frame.is_down(&Key::DownPlayer2));
+        assert!(frame.is_down(&Key::RightPlayer2));
+        assert!(!frame.is_down(&Key::Down));
+        assert!(!frame.is_down(&Key::Right));
+        // The third controller isn't assigned, it plays as player 1.
+        assert!(frame.is_down(&Key::Up));
+    }
+
+    #[test]
+    fn presses_and_releases_last_one_frame() {
+        let (mut gamepads, fake) = gamepads();
+        fake.set(vec![controller(&[Control::South])]);
+        let frame = gamepads.update();
+        assert!(frame.is_pressed(&Key::Space) && frame.is_down(&Key::Space));
+
+        let frame = gamepads.update();
+        assert!(!frame.is_pressed(&Key::Space) && frame.is_down(&Key::Space));
+
+        fake.set(vec![controller(&[])]);
+        let frame = gamepads.update();
+        assert!(frame.is_released(&Key::Space) && !frame.is_down(&Key::Space));
+
+        let frame = gamepads.update();
+        assert!(!frame.is_released(&Key::Space));
+    }
+
+    #[test]
+    fn unplugged_controllers_release_their_actions() {
+        let (mut gamepads, fake) = gamepads();
+        fake.set(vec![controller(&[Control::RightTrigger])]);
+        assert!(gamepads.update().is_down(&Key::Forward));
+        fake.set(Vec::new());
+        assert!(gamepads.update().is_released(&Key::Forward));
+    }
+}
@@ -0,0 +1,182 @@
+//! A [`Graphic`] backend without a window, driving the games with scripted
+//! key presses and keeping the last buffer they drew.
+
+use graphic::Graphic;
+use web_time::Duration;
+use window_rs::WindowBuffer;
+
+use crate::{
+    common::{GameInput, ManualClock},
+    game::Game,
+    keymap::{index, ActionFrame, ACTIONS},
+};
+
+pub struct Headless {
+    width: usize,
+    height: usize,
+    open: bool,
+    /// The actions held from the next frame on.
+    held: [bool; ACTIONS.len()],
+    frame: ActionFrame,
+    /// The cell clicked with each [`graphic::Mouse`] button this frame.
+    clicks: [Option<(f32, f32)>; 3],
+    next_clicks: [Option<(f32, f32)>; 3],
+    buffer: Option<WindowBuffer>,
+    frames: usize,
+}
+
+impl Headless {
+    /// Starts holding `action` from the next frame on.
+    pub fn hold(&mut self, action: &graphic::Key) {
+        self.held[index(action)] = true;
+    }
+
+    /// Stops holding `action` from the next frame on.
+    pub fn release(&mut self, action: &graphic::Key) {
+        self.held[index(action)] = false;
+    }
+
+    /// Clicks the `(x, y)` cell with `mouse` during the next frame.
+    pub fn click(&mut self, mouse: &graphic::Mouse, cell: (usize, usize)) {
+        self.next_clicks[mouse_index(mouse)] = Some((cell.0 as f32, cell.1 as f32));
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Moves on to the next frame, with the actions held and the clicks made
+    /// since the last one.
+    pub fn next_frame(&mut self) {
+        self.frame = self.frame.next(self.held);
+        self.clicks = std::mem::take(&mut self.next_clicks);
+        self.frames += 1;
+    }
+
+    /// Plays one frame of `game`: its input, a single tick whatever its
+    /// scheduler says, and the buffer it drew.
+    pub fn step(&mut self, game: &mut dyn Game) {
+        self.next_frame();
+        game.handle_input(self);
+        game.tick();
+        self.update_with_buffer(game.buffer());
+    }
+
+    /// Plays one frame of `game` lasting `elapsed` on `clock`, the clock given
+    /// to [`Game::set_clock`]: its input and the ticks due as in the app.
+    /// Returns the number of ticks played.
+    pub fn play(&mut self, game: &mut dyn Game, clock: &ManualClock, elapsed: Duration) -> u32 {
+        clock.advance(elapsed);
+        self.next_frame();
+        game.handle_input(self);
+        let ticks = game.scheduler_mut().due_ticks();
+        for _ in 0..ticks {
+            game.tick();
+        }
+        self.update_with_buffer(game.buffer());
+        ticks
+    }
+
+    /// Plays `frames` frames of `game` with the same actions held.
+    pub fn run(&mut self, game: &mut dyn Game, frames: usize) {
+        for _ in 0..frames {
+            self.step(game);
+        }
+    }
+
+    /// The last buffer given to [`Graphic::update_with_buffer`].
+    pub fn buffer(&self) -> Option<&WindowBuffer> {
+        self.buffer.as_ref()
+    }
+
+    /// The number of frames played so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl Graphic for Headless {
+    fn new(_name: &str, width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            open: true,
+            held: [false; ACTIONS.len()],
+            frame: ActionFrame::default(),
+            clicks: [None; 3],
+            next_clicks: [None; 3],
+            buffer: None,
+            frames: 0,
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_key_down(&self, key: graphic::Key) -> bool {
+        self.frame.is_down(&key)
+    }
+
+    fn update_with_buffer(&mut self, windows: &WindowBuffer) {
+        self.buffer = Some(windows.clone());
+    }
+
+    fn is_key_pressed(&self, key: graphic::Key) -> bool {
+        self.frame.is_pressed(&key)
+    }
+
+    fn get_keys_released(&self) -> Vec<graphic::Key> {
+        ACTIONS
+            .into_iter()
+            .filter(|action| self.frame.is_released(action))
+            .collect()
+    }
+
+    fn get_mouse_pos(&self, mouse: graphic::Mouse) -> Option<(f32, f32)> {
+        self.clicks[mouse_index(&mouse)]
+    }
+
+    fn get_mouse_down(&self, mouse: graphic::Mouse) -> bool {
+        self.clicks[mouse_index(&mouse)].is_some()
+    }
+}
+
+/// The games read the actions held and the cells clicked.
+impl GameInput for Headless {
+    fn is_key_down(&self, key: graphic::Key) -> bool {
+        Graphic::is_key_down(self, key)
+    }
+
+    fn is_key_pressed(&self, key: graphic::Key) -> bool {
+        Graphic::is_key_pressed(self, key)
+    }
+
+    fn get_keys_released(&self) -> Vec<graphic::Key> {
+        Graphic::get_keys_released(self)
+    }
+
+    fn get_mouse_pos(&self, mouse: graphic::Mouse) -> Option<(f32, f32)> {
+        Graphic::get_mouse_pos(self, mouse)
+    }
+
+    fn get_mouse_down(&self, mouse: graphic::Mouse) -> bool {
+        Graphic::get_mouse_down(self, mouse)
+    }
+}
+
+fn mouse_index(mouse: &graphic::Mouse) -> usize {
+    match mouse {
+        graphic::Mouse::Left => 0,
+        graphic::Mouse::Right => 1,
+        graphic::Mouse::Discard => 2,
+    }
+}
@@ -0,0 +1,166 @@
+//! The boards of the last Snake ticks, previewed on the timeline.
+//!
+//! A board is mostly empty, so each one is kept as runs of cells of the same
+//! colour rather than as a [`WindowBuffer`] the size of the board.
+
+use std::collections::VecDeque;
+use window_rs::WindowBuffer;
+
+/// Ticks kept, the older ones can't be previewed.
+const MAX_TICKS: usize = 2000;
+/// Size of the boards kept past which the older ones are dropped.
+const MAX_BYTES: usize = 16 << 20;
+
+/// A board as `(colour, cells)` runs, in reading order.
+struct Snapshot {
+    runs: Vec<(u32, u32)>,
+}
+
+impl Snapshot {
+    fn new(buffer: &WindowBuffer) -> Self {
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+        for y in 0..buffer.height() {
+            for x in 0..buffer.width() {
+                let colour = buffer[(x, y)];
+                match runs.last_mut() {
+                    Some((run_colour, cells)) if *run_colour == colour => *cells += 1,
+                    _ => runs.push((colour, 1)),
+                }
+            }
+        }
+        Self { runs }
+    }
+
+    fn bytes(&self) -> usize {
+        self.runs.len() * std::mem::size_of::<(u32, u32)>()
+    }
+
+    fn to_buffer(&self, width: usize, height: usize) -> WindowBuffer {
+        let mut buffer = WindowBuffer::new(width, height);
+        let mut cell = 0;
+        for &(colour, cells) in &self.runs {
+            for _ in 0..cells {
+                buffer[(cell % width, cell / width)] = colour;
+                cell += 1;
+            }
+        }
+        buffer
+    }
+}
+
+#[derive(Default)]
+pub struct History {
+    snapshots: VecDeque<Snapshot>,
+    bytes: usize,
+}
+
+impl History {
+    /// Keeps the board after a tick, dropping the oldest ones over the limits.
+    pub fn push(&mut self, buffer: &WindowBuffer) {
+        let snapshot = Snapshot::new(buffer);
+        self.bytes += snapshot.bytes();
+        self.snapshots.push_back(snapshot);
+        while self.snapshots.len() > MAX_TICKS || self.bytes > MAX_BYTES {
+            match self.snapshots.pop_front() {
+                Some(oldest) => self.bytes -= oldest.bytes(),
+                None => break,
+            }
+        }
+    }
+
+    /// Forgets the last tick, `false` when there is none.
+    pub fn pop(&mut self) -> bool {
+        match self.snapshots.pop_back() {
+            Some(last) => {
+                self.bytes -= last.bytes();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// The board after the `tick`th tick kept, counted from 1, on a board of
+    /// `width` by `height`.
+    pub fn board(&self, tick: usize, width: usize, height: usize) -> Option<WindowBuffer> {
+        let snapshot = self.snapshots.get(tick.checked_sub(1)?)?;
+        Some(snapshot.to_buffer(width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(cells: &[((usize, usize), u32)]) -> WindowBuffer {
+        let mut buffer = WindowBuffer::new(7, 5);
+        for &(cell, colour) in cells {
+            buffer[cell] = colour;
+        }
+        buffer
+    }
+
+    #[test]
+    fn boards_come_back_as_they_were() {
+        let mut history = History::default();
+        let boards = [
+            board(&[]),
+            board(&[((0, 0), 1), ((6, 4), 2)]),
+            board(&[((2, 1), 3), ((3, 1), 3), ((6, 1), 4), ((0, 2), 4)]),
+        ];
+        for board in &boards {
+            history.push(board);
+        }
+        assert_eq!(history.len(), 3);
+        for (tick, board) in boards.iter().enumerate() {
+            assert_eq!(history.board(tick + 1, 7, 5).as_ref(), Some(board));
+        }
+        assert!(history.board(0, 7, 5).is_none());
+        assert!(history.board(4, 7, 5).is_none());
+    }
+
+    #[test]
+    fn empty_cells_take_one_run() {
+        assert_eq!(
+            Snapshot::new(&WindowBuffer::new(200, 200)).runs,
+            [(0, 40_000)]
+        );
+    }
+
+    #[test]
+    fn the_oldest_ticks_are_dropped() {
+        let mut history = History::default();
+        for tick in 0..MAX_TICKS + 10 {
+            history.push(&board(&[((tick % 7, 0), 1)]));
+        }
+        assert_eq!(history.len(), MAX_TICKS);
+        assert_eq!(history.board(1, 7, 5), Some(board(&[((10 % 7, 0), 1)])));
+    }
+
+    #[test]
+    fn history_stays_under_its_size() {
+        let mut history = History::default();
+        // Every cell of a different colour than the next one.
+        let mut busy = WindowBuffer::new(512, 512);
+        for y in 0..512 {
+            for x in 0..512 {
+                busy[(x, y)] = ((x + y) % 2) as u32;
+            }
+        }
+        for _ in 0..20 {
+            history.push(&busy);
+        }
+        assert!(history.bytes <= MAX_BYTES);
+        assert_eq!(history.len(), MAX_BYTES / (512 * 512 * 8));
+
+        while history.pop() {}
+        assert_eq!((history.len(), history.bytes), (0, 0));
+    }
+}
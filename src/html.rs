@@ -0,0 +1,256 @@
+//! Static HTML rendering of a [`Cv`], readable by search engines, screen
+//! readers and ATS scrapers which can't see through the canvas.
+
+use std::fmt::Write;
+
+use crate::cv::{spans, Cv};
+
+impl Cv {
+    /// Renders the resume, written in `locale`, as a standalone, semantic HTML page.
+    pub fn to_html(&self, locale: &str) -> String {
+        let mut html = String::new();
+        // Writing into a `String` can't fail.
+        self.write_html(&mut html, locale).unwrap();
+        html
+    }
+
+    fn write_html(&self, html: &mut String, locale: &str) -> std::fmt::Result {
+        let name = escape(&self.identity.name);
+        let headline = escape(&self.identity.headline);
+        let labels = &self.labels;
+
+        writeln!(html, "<!DOCTYPE html>")?;
+        writeln!(html, "<html lang=\"{}\">", escape(locale))?;
+        writeln!(html, "<head>")?;
+        writeln!(html, "<meta charset=\"utf-8\">")?;
+        writeln!(
+            html,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+        )?;
+        writeln!(html, "<title>{name}</title>")?;
+        writeln!(html, "<meta name=\"description\" content=\"{headline}\">")?;
+        writeln!(html, "</head>")?;
+        writeln!(html, "<body>")?;
+
+        writeln!(html, "<header>")?;
+        writeln!(html, "<h1>{name}</h1>")?;
+        writeln!(html, "<p>{headline}</p>")?;
+        writeln!(html, "</header>")?;
+
+        writeln!(html, "<main>")?;
+        writeln!(html, "<section id=\"summary\">")?;
+        writeln!(html, "<h2>{}</h2>", escape(&labels.summary_section))?;
+        for paragraph in &self.summary {
+            writeln!(html, "<p>{}</p>", rich_text(paragraph))?;
+        }
+        writeln!(html, "</section>")?;
+
+        writeln!(html, "<section id=\"experience\">")?;
+        writeln!(html, "<h2>{}</h2>", escape(&labels.experience_section))?;
+        for experience in &self.experience {
+            writeln!(html, "<article>")?;
+            writeln!(html, "<h3>{}</h3>", escape(&labels.position_at(experience)))?;
+            writeln!(html, "<p>{}</p>", rich_text(&experience.summary))?;
+            writeln!(html, "</article>")?;
+        }
+        writeln!(html, "</section>")?;
+
+        writeln!(html, "<section id=\"education\">")?;
+        writeln!(html, "<h2>{}</h2>", escape(&labels.education_section))?;
+        for education in &self.education {
+            writeln!(html, "<article>")?;
+            writeln!(
+                html,
+                "<h3>{}, {}</h3>",
+                escape(&education.study_type),
+                escape(&education.institution)
+            )?;
+            writeln!(html, "<p>{}</p>", rich_text(&education.summary))?;
+            writeln!(html, "</article>")?;
+        }
+        writeln!(html, "</section>")?;
+
+        writeln!(html, "<section id=\"projects\">")?;
+        writeln!(html, "<h2>{}</h2>", escape(&labels.projects_section))?;
+        for project in &self.projects {
+            writeln!(html, "<article>")?;
+            match &project.url {
+                Some(url) => writeln!(
+                    html,
+                    "<h3><a href=\"{}\">{}</a></h3>",
+                    escape(url),
+                    escape(&project.name)
+                )?,
+                None => writeln!(html, "<h3>{}</h3>", escape(&project.name))?,
+            }
+            if !project.description.is_empty() {
+                writeln!(html, "<p>{}</p>", rich_text(&project.description))?;
+            }
+            if !project.keywords.is_empty() {
+                writeln!(
+                    html,
+                    "<p>{}: {}</p>",
+                    escape(&labels.frameworks),
+                    escape(&project.keywords.join(", "))
+                )?;
+            }
+            if !project.highlights.is_empty() {
+                writeln!(html, "<ul>")?;
+                for highlight in &project.highlights {
+                    if highlight.name.is_empty() {
+                        writeln!(html, "<li>{}</li>", rich_text(&highlight.description))?;
+                    } else {
+                        writeln!(
+                            html,
+                            "<li><strong>{}:</strong> {}</li>",
+                            escape(&highlight.name),
+                            rich_text(&highlight.description)
+                        )?;
+                    }
+                }
+                writeln!(html, "</ul>")?;
+            }
+            writeln!(html, "</article>")?;
+        }
+        writeln!(html, "</section>")?;
+        writeln!(html, "</main>")?;
+
+        writeln!(html, "<aside>")?;
+        writeln!(html, "<h2>{}</h2>", escape(&labels.at_a_glance))?;
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h3>{}</h3>", escape(&labels.languages_spoken))?;
+        writeln!(html, "<ul>")?;
+        for language in &self.languages {
+            writeln!(
+                html,
+                "<li>{} ({})</li>",
+                escape(&language.language),
+                escape(&language.fluency)
+            )?;
+        }
+        writeln!(html, "</ul>")?;
+        writeln!(html, "</section>")?;
+        for group in &self.skills {
+            writeln!(html, "<section>")?;
+            writeln!(html, "<h3>{}</h3>", escape(&group.name))?;
+            writeln!(html, "<ul>")?;
+            for keyword in &group.keywords {
+                writeln!(html, "<li>{}</li>", escape(keyword))?;
+            }
+            writeln!(html, "</ul>")?;
+            writeln!(html, "</section>")?;
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h3>{}</h3>", escape(&labels.social_media))?;
+        writeln!(html, "<ul>")?;
+        for link in &self.links {
+            writeln!(
+                html,
+                "<li><a href=\"{}\">{}</a></li>",
+                escape(&link.url),
+                escape(&link.network)
+            )?;
+        }
+        writeln!(html, "</ul>")?;
+        writeln!(html, "</section>")?;
+        writeln!(html, "</aside>")?;
+
+        writeln!(html, "</body>")?;
+        writeln!(html, "</html>")
+    }
+}
+
+/// Escapes `text` and turns its `**strong**` spans into `<strong>` elements.
+fn rich_text(text: &str) -> String {
+    spans(text)
+        .map(|(segment, strong)| {
+            if strong {
+                format!("<strong>{}</strong>", escape(segment))
+            } else {
+                escape(segment)
+            }
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cv::{Highlight, Identity, Link, Project};
+
+    /// Compares `actual` with the file `tests/snapshots/{name}`, which is
+    /// written instead when `UPDATE_SNAPSHOTS` is set.
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/snapshots")
+            .join(name);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            expected == actual,
+            "{} is outdated, run the tests with UPDATE_SNAPSHOTS=1 and review the diff",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn embedded_resume_snapshot() {
+        let cv = Cv::embedded().unwrap();
+        assert_snapshot("resume.html", &cv.to_html("en"));
+        assert_snapshot("resume.fr.html", &cv.localized("fr").to_html("fr"));
+    }
+
+    #[test]
+    fn escapes_names_urls_and_strong_spans() {
+        let cv = Cv {
+            identity: Identity {
+                name: "Tom & \"Jerry\" <3".to_string(),
+                headline: String::new(),
+            },
+            summary: vec!["Cats **<b>&\"dogs\"** and mice".to_string()],
+            projects: vec![Project {
+                name: "A<B".to_string(),
+                url: Some("https://example.com/?a=1&b=\"2\"".to_string()),
+                highlights: vec![Highlight {
+                    name: "x > y".to_string(),
+                    description: "**&**".to_string(),
+                }],
+                ..Default::default()
+            }],
+            links: vec![Link {
+                network: "<script>".to_string(),
+                url: "javascript:\"".to_string(),
+            }],
+            ..Default::default()
+        };
+        let html = cv.to_html("en");
+        assert!(html.contains("<title>Tom &amp; &quot;Jerry&quot; &lt;3</title>"));
+        assert!(html.contains("<h1>Tom &amp; &quot;Jerry&quot; &lt;3</h1>"));
+        assert!(
+            html.contains("<p>Cats <strong>&lt;b&gt;&amp;&quot;dogs&quot;</strong> and mice</p>")
+        );
+        assert!(
+            html.contains("<a href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\">A&lt;B</a>")
+        );
+        assert!(html.contains("<li><strong>x &gt; y:</strong> <strong>&amp;</strong></li>"));
+        assert!(html.contains("<a href=\"javascript:&quot;\">&lt;script&gt;</a>"));
+        assert!(!html.contains("<script>"));
+    }
+}
@@ -0,0 +1,349 @@
+//! Conversion between [`Cv`] and the [JSON Resume](https://jsonresume.org/schema) schema.
+//!
+//! Only the fields the resume page displays and the schema has are mapped,
+//! everything else is ignored on import. Project highlights are plain strings
+//! in the schema, a named one is written as `"**name**: description"` and only
+//! highlights written this way are split on import.
+
+use serde::{Deserialize, Serialize};
+
+use crate::cv::{
+    Cv, Education, Experience, Highlight, Identity, Language, Link, Project, SkillGroup,
+};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonResume {
+    basics: Basics,
+    work: Vec<Work>,
+    education: Vec<JsonEducation>,
+    projects: Vec<JsonProject>,
+    skills: Vec<Skill>,
+    languages: Vec<JsonLanguage>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Basics {
+    name: String,
+    label: String,
+    summary: String,
+    profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Profile {
+    network: String,
+    url: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Work {
+    name: String,
+    position: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    summary: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonEducation {
+    institution: String,
+    area: String,
+    study_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct JsonProject {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    description: String,
+    keywords: Vec<String>,
+    highlights: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Skill {
+    name: String,
+    keywords: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct JsonLanguage {
+    language: String,
+    fluency: String,
+}
+
+impl From<&Cv> for JsonResume {
+    fn from(cv: &Cv) -> Self {
+        JsonResume {
+            basics: Basics {
+                name: cv.identity.name.clone(),
+                label: cv.identity.headline.clone(),
+                summary: cv.summary.join("\n\n"),
+                profiles: cv
+                    .links
+                    .iter()
+                    .map(|link| Profile {
+                        network: link.network.clone(),
+                        url: link.url.clone(),
+                    })
+                    .collect(),
+            },
+            work: cv
+                .experience
+                .iter()
+                .map(|experience| Work {
+                    name: experience.company.clone(),
+                    position: experience.position.clone(),
+                    start_date: experience.start.clone(),
+                    end_date: experience.end.clone(),
+                    summary: experience.summary.clone(),
+                })
+                .collect(),
+            education: cv
+                .education
+                .iter()
+                .map(|education| JsonEducation {
+                    institution: education.institution.clone(),
+                    area: education.area.clone(),
+                    study_type: education.study_type.clone(),
+                    start_date: education.start.clone(),
+                })
+                .collect(),
+            projects: cv
+                .projects
+                .iter()
+                .map(|project| JsonProject {
+                    name: project.name.clone(),
+                    url: project.url.clone(),
+                    description: project.description.clone(),
+                    keywords: project.keywords.clone(),
+                    highlights: project
+                        .highlights
+                        .iter()
+                        .map(|highlight| {
+                            if highlight.name.is_empty() {
+                                highlight.description.clone()
+                            } else {
+                                format!("**{}**: {}", highlight.name, highlight.description)
+                            }
+                        })
+                        .collect(),
+                })
+                .collect(),
+            skills: cv
+                .skills
+                .iter()
+                .map(|group| Skill {
+                    name: group.name.clone(),
+                    keywords: group.keywords.clone(),
+                })
+                .collect(),
+            languages: cv
+                .languages
+                .iter()
+                .map(|language| JsonLanguage {
+                    language: language.language.clone(),
+                    fluency: language.fluency.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<JsonResume> for Cv {
+    fn from(resume: JsonResume) -> Self {
+        let JsonResume {
+            basics,
+            work,
+            education,
+            projects,
+            skills,
+            languages,
+        } = resume;
+
+        Cv {
+            identity: Identity {
+                name: basics.name,
+                headline: basics.label,
+            },
+            summary: basics
+                .summary
+                .split("\n\n")
+                .filter(|paragraph| !paragraph.trim().is_empty())
+                .map(str::to_string)
+                .collect(),
+            experience: work
+                .into_iter()
+                .map(|work| Experience {
+                    company: work.name,
+                    position: work.position,
+                    start: work.start_date,
+                    end: work.end_date,
+                    summary: work.summary,
+                })
+                .collect(),
+            education: education
+                .into_iter()
+                .map(|education| Education {
+                    institution: education.institution,
+                    area: education.area,
+                    study_type: education.study_type,
+                    start: education.start_date,
+                    summary: String::new(),
+                })
+                .collect(),
+            projects: projects
+                .into_iter()
+                .map(|project| Project {
+                    name: project.name,
+                    url: project.url,
+                    description: project.description,
+                    keywords: project.keywords,
+                    highlights: project.highlights.iter().map(|h| highlight(h)).collect(),
+                })
+                .collect(),
+            skills: skills
+                .into_iter()
+                .map(|skill| SkillGroup {
+                    name: skill.name,
+                    keywords: skill.keywords,
+                })
+                .collect(),
+            languages: languages
+                .into_iter()
+                .map(|language| Language {
+                    language: language.language,
+                    fluency: language.fluency,
+                })
+                .collect(),
+            links: basics
+                .profiles
+                .into_iter()
+                .map(|profile| Link {
+                    network: profile.network,
+                    url: profile.url,
+                })
+                .collect(),
+            ..Cv::default()
+        }
+    }
+}
+
+/// Splits a `"**name**: description"` highlight, any other highlight only has a description.
+fn highlight(highlight: &str) -> Highlight {
+    let named = highlight
+        .strip_prefix("**")
+        .and_then(|highlight| highlight.split_once("**: "));
+    match named {
+        Some((name, description)) => Highlight {
+            name: name.to_string(),
+            description: description.to_string(),
+        },
+        None => Highlight {
+            name: String::new(),
+            description: highlight.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_resume_round_trips() {
+        let cv = Cv::embedded().unwrap();
+        let imported = Cv::from_json(&cv.to_json_resume()).unwrap();
+        let mut expected = Cv {
+            labels: Default::default(),
+            translations: Default::default(),
+            ..cv
+        };
+        // The schema has no summary for education.
+        for education in &mut expected.education {
+            education.summary.clear();
+        }
+        assert_eq!(imported, expected);
+    }
+
+    #[test]
+    fn highlights_round_trip() {
+        let highlights = vec![
+            Highlight {
+                name: "Ghost mode".to_string(),
+                description: "go through walls".to_string(),
+            },
+            Highlight {
+                name: String::new(),
+                description: "Speed: up to 60 frames per second".to_string(),
+            },
+            Highlight {
+                name: String::new(),
+                description: "No name".to_string(),
+            },
+            Highlight {
+                name: "Bold: **name**".to_string(),
+                description: "with a colon: and more".to_string(),
+            },
+        ];
+        let cv = Cv {
+            projects: vec![Project {
+                highlights: highlights.clone(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let imported = Cv::from_json(&cv.to_json_resume()).unwrap();
+        assert_eq!(imported.projects[0].highlights, highlights);
+    }
+
+    #[test]
+    fn imported_highlights_stay_whole() {
+        let json = r#"{"basics": {}, "projects": [{"highlights": ["Stack: Rust, Go", "**Rust** everywhere"]}]}"#;
+        let cv = Cv::from_json(json).unwrap();
+        let descriptions: Vec<_> = cv.projects[0]
+            .highlights
+            .iter()
+            .map(|highlight| (highlight.name.as_str(), highlight.description.as_str()))
+            .collect();
+        assert_eq!(
+            descriptions,
+            [("", "Stack: Rust, Go"), ("", "**Rust** everywhere")]
+        );
+    }
+
+    #[test]
+    fn imports_the_json_resume_sample() {
+        let cv = Cv::from_json(include_str!("../tests/fixtures/json_resume_sample.json")).unwrap();
+        assert_eq!(cv.identity.name, "Richard Hendriks");
+        assert_eq!(cv.identity.headline, "Programmer");
+        assert_eq!(cv.summary.len(), 1);
+        assert_eq!(cv.experience[0].company, "Pied Piper");
+        assert_eq!(cv.experience[0].start.as_deref(), Some("2013-12-01"));
+        assert_eq!(cv.education[0].study_type, "Bachelor");
+        assert_eq!(cv.skills[1].keywords, ["Mpeg", "MP4", "GIF"]);
+        assert_eq!(cv.languages[0].fluency, "Native speaker");
+        assert_eq!(cv.links[1].network, "SoundCloud");
+        assert_eq!(cv.projects[0].name, "Miss Direction");
+        assert_eq!(
+            cv.projects[0].highlights[0],
+            Highlight {
+                name: String::new(),
+                description: "Won award at AIHacks 2016".to_string(),
+            }
+        );
+    }
+}
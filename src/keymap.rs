@@ -0,0 +1,280 @@
+//! The keyboard keys triggering each [`graphic::Key`] action of the games,
+//! edited in the "Controls" view and saved with the app state.
+
+use egui::{Key, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::common::GameInput;
+
+/// Every game action, in the order of the "Controls" view.
+pub const ACTIONS: [graphic::Key; 17] = [
+    graphic::Key::Up,
+    graphic::Key::Down,
+    graphic::Key::Left,
+    graphic::Key::Right,
+    graphic::Key::Space,
+    graphic::Key::Escape,
+    graphic::Key::Quit,
+    graphic::Key::UpPlayer1,
+    graphic::Key::DownPlayer1,
+    graphic::Key::UpPlayer2,
+    graphic::Key::DownPlayer2,
+    graphic::Key::LeftPlayer2,
+    graphic::Key::RightPlayer2,
+    graphic::Key::Launch,
+    graphic::Key::Forward,
+    graphic::Key::Backward,
+    graphic::Key::Save,
+];
+
+/// Position of `action` in [`ACTIONS`].
+pub(crate) fn index(action: &graphic::Key) -> usize {
+    match action {
+        graphic::Key::Up => 0,
+        graphic::Key::Down => 1,
+        graphic::Key::Left => 2,
+        graphic::Key::Right => 3,
+        graphic::Key::Space => 4,
+        graphic::Key::Escape => 5,
+        graphic::Key::Quit => 6,
+        graphic::Key::UpPlayer1 => 7,
+        graphic::Key::DownPlayer1 => 8,
+        graphic::Key::UpPlayer2 => 9,
+        graphic::Key::DownPlayer2 => 10,
+        graphic::Key::LeftPlayer2 => 11,
+        graphic::Key::RightPlayer2 => 12,
+        graphic::Key::Launch => 13,
+        graphic::Key::Forward => 14,
+        graphic::Key::Backward => 15,
+        graphic::Key::Save => 16,
+    }
+}
+
+pub fn action_name(action: &graphic::Key) -> &'static str {
+    match action {
+        graphic::Key::Up => "Up",
+        graphic::Key::Down => "Down",
+        graphic::Key::Left => "Left",
+        graphic::Key::Right => "Right",
+        graphic::Key::Space => "Space",
+        graphic::Key::Escape => "Escape",
+        graphic::Key::Quit => "Quit",
+        graphic::Key::UpPlayer1 => "Player 1 up",
+        graphic::Key::DownPlayer1 => "Player 1 down",
+        graphic::Key::UpPlayer2 => "Player 2 up",
+        graphic::Key::DownPlayer2 => "Player 2 down",
+        graphic::Key::LeftPlayer2 => "Player 2 left",
+        graphic::Key::RightPlayer2 => "Player 2 right",
+        graphic::Key::Launch => "Launch",
+        graphic::Key::Forward => "Forward in time",
+        graphic::Key::Backward => "Backward in time",
+        graphic::Key::Save => "Save",
+    }
+}
+
+/// The actions triggered during a frame by other means than the keyboard,
+/// controllers or touch controls, indexed like [`ACTIONS`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActionFrame {
+    down: [bool; ACTIONS.len()],
+    pressed: [bool; ACTIONS.len()],
+    released: [bool; ACTIONS.len()],
+}
+
+impl ActionFrame {
+    /// The frame following this one, with the actions held in `down`.
+    pub fn next(&self, down: [bool; ACTIONS.len()]) -> Self {
+        Self {
+            down,
+            pressed: std::array::from_fn(|i| down[i] && !self.down[i]),
+            released: std::array::from_fn(|i| !down[i] && self.down[i]),
+        }
+    }
+
+    /// The actions of both frames.
+    pub fn or(&self, other: &ActionFrame) -> Self {
+        Self {
+            down: std::array::from_fn(|i| self.down[i] || other.down[i]),
+            pressed: std::array::from_fn(|i| self.pressed[i] || other.pressed[i]),
+            released: std::array::from_fn(|i| self.released[i] || other.released[i]),
+        }
+    }
+
+    pub fn is_down(&self, action: &graphic::Key) -> bool {
+        self.down[index(action)]
+    }
+
+    /// Whether `action` started this frame.
+    pub fn is_pressed(&self, action: &graphic::Key) -> bool {
+        self.pressed[index(action)]
+    }
+
+    /// Whether `action` stopped this frame.
+    pub fn is_released(&self, action: &graphic::Key) -> bool {
+        self.released[index(action)]
+    }
+}
+
+/// The actions alone, without a mouse.
+impl GameInput for ActionFrame {
+    fn is_key_down(&self, key: graphic::Key) -> bool {
+        self.is_down(&key)
+    }
+
+    fn is_key_pressed(&self, key: graphic::Key) -> bool {
+        self.is_pressed(&key)
+    }
+
+    fn get_keys_released(&self) -> Vec<graphic::Key> {
+        ACTIONS
+            .into_iter()
+            .filter(|action| self.is_released(action))
+            .collect()
+    }
+
+    fn get_mouse_pos(&self, _mouse: graphic::Mouse) -> Option<(f32, f32)> {
+        None
+    }
+
+    fn get_mouse_down(&self, _mouse: graphic::Mouse) -> bool {
+        false
+    }
+}
+
+/// Keys laid out for a keyboard layout, player 2 on the right of the
+/// letters and the launch key at the bottom left.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Azerty,
+    Qwerty,
+    Qwertz,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 3] = [Preset::Azerty, Preset::Qwerty, Preset::Qwertz];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Azerty => "AZERTY",
+            Preset::Qwerty => "QWERTY",
+            Preset::Qwertz => "QWERTZ",
+        }
+    }
+
+    pub fn keymap(self) -> Keymap {
+        // Up, down, left and right of player 2, then launch.
+        let [up_2, down_2, left_2, right_2, launch] = match self {
+            Preset::Azerty => [Key::O, Key::L, Key::K, Key::M, Key::W],
+            Preset::Qwerty => [Key::I, Key::K, Key::J, Key::L, Key::Z],
+            Preset::Qwertz => [Key::I, Key::K, Key::J, Key::L, Key::Y],
+        };
+        Keymap {
+            keys: [
+                Key::ArrowUp,
+                Key::ArrowDown,
+                Key::ArrowLeft,
+                Key::ArrowRight,
+                Key::Space,
+                Key::Escape,
+                Key::Delete,
+                Key::E,
+                Key::D,
+                up_2,
+                down_2,
+                left_2,
+                right_2,
+                launch,
+                Key::F,
+                Key::R,
+                Key::S,
+            ],
+        }
+    }
+}
+
+/// The key bound to each action, indexed like [`ACTIONS`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keymap {
+    keys: [Key; ACTIONS.len()],
+}
+
+impl Default for Keymap {
+    /// The bindings the games were designed with, on an AZERTY keyboard.
+    fn default() -> Self {
+        Preset::Azerty.keymap()
+    }
+}
+
+impl Keymap {
+    pub fn key(&self, action: &graphic::Key) -> Key {
+        self.keys[index(action)]
+    }
+
+    pub fn set(&mut self, action: &graphic::Key, key: Key) {
+        self.keys[index(action)] = key;
+    }
+
+    /// The other actions bound to the same key as `action`.
+    pub fn conflicts(&self, action: &graphic::Key) -> Vec<&'static graphic::Key> {
+        let key = self.key(action);
+        ACTIONS
+            .iter()
+            .filter(|other| index(other) != index(action) && self.key(other) == key)
+            .collect()
+    }
+
+    /// Lists every action with its key. Clicking a key waits for the next key
+    /// press to bind it, `rebinding` is the index of the action waiting.
+    pub fn ui(&mut self, ui: &mut Ui, rebinding: &mut Option<usize>) {
+        ui.horizontal(|ui| {
+            ui.label("Presets:");
+            for preset in Preset::ALL {
+                if ui.button(preset.name()).clicked() {
+                    *self = preset.keymap();
+                    *rebinding = None;
+                }
+            }
+        });
+        ui.separator();
+
+        if let Some(waiting) = *rebinding {
+            let pressed = ui.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => Some(*key),
+                    _ => None,
+                })
+            });
+            if let Some(key) = pressed {
+                self.keys[waiting] = key;
+                *rebinding = None;
+            }
+        }
+
+        egui::Grid::new("keymap").striped(true).show(ui, |ui| {
+            for (i, action) in ACTIONS.iter().enumerate() {
+                ui.label(action_name(action));
+                if *rebinding == Some(i) {
+                    ui.label("Press a key…");
+                    if ui.button("Cancel").clicked() {
+                        *rebinding = None;
+                    }
+                } else {
+                    if ui.button(self.key(action).name()).clicked() {
+                        *rebinding = Some(i);
+                    }
+                    let conflicts = self.conflicts(action);
+                    if !conflicts.is_empty() {
+                        let names: Vec<_> = conflicts.into_iter().map(action_name).collect();
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            format!("Also bound to {}", names.join(", ")),
+                        );
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -1,9 +1,31 @@
+mod bot;
 mod common;
 mod cv;
+mod fonts;
+mod game;
+mod gamepad;
+mod headless;
+mod history;
+mod html;
+mod json_resume;
+mod keymap;
 mod life;
+mod locale;
 mod maze;
+mod pdf;
 mod pong;
+mod replay;
 mod resume;
 mod snake;
-pub use common::{draw_window_buffer, InputWrapper};
+mod text;
+mod touch;
+pub use common::{
+    draw_window_buffer, draw_window_buffer_rects, BoardLayout, Clock, FrameInput, GameInput,
+    InputWrapper, ManualClock, RealClock, Scheduler,
+};
+pub use cv::Cv;
+pub use game::{registry, Game};
+pub use gamepad::{Control, Controller, FakeGamepads, GamepadSource, Gamepads, Player};
+pub use headless::Headless;
+pub use keymap::{ActionFrame, Keymap, Preset, ACTIONS};
 pub use resume::Resume;
@@ -1,15 +1,33 @@
 use egui::Ui;
 use game_of_life::{Cli, World};
+use serde::{Deserialize, Serialize};
 use web_time::{Duration, Instant};
 use window_rs::WindowBuffer;
 
-use crate::{common::colour_changer, draw_window_buffer, InputWrapper};
+use crate::{
+    common::{colour_changer, GameInput, InputWrapper, Scheduler},
+    game::Game,
+};
 
 pub struct Life {
     cli: Cli,
-    buffer: WindowBuffer,
+    /// Draws its own board, clicks and generations included.
     config: World,
-    time_check: Instant,
+    /// Ticks once per generation, every `config.speed()` seconds.
+    scheduler: Scheduler,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifeOptions {
+    colour_cell: u32,
+    speed: u64,
+}
+
+impl Default for LifeOptions {
+    fn default() -> Self {
+        Life::default().options()
+    }
 }
 
 impl Default for Life {
@@ -19,7 +37,6 @@ impl Default for Life {
             height: 30,
             file_path: None,
         };
-        let buffer: WindowBuffer = WindowBuffer::new(cli.width, cli.height);
         let config = World::new(
             WindowBuffer::new(cli.width, cli.height),
             0,
@@ -27,23 +44,64 @@ impl Default for Life {
             2,
             0x0066CC33,
         );
-        let time_check = Instant::now();
+        let scheduler = Scheduler::new(Duration::from_secs(config.speed()));
 
         Self {
             cli,
-            buffer,
             config,
-            time_check,
+            scheduler,
         }
     }
 }
 
 impl Life {
-    pub fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
+    pub fn with_options(options: LifeOptions) -> Self {
+        let mut life = Life::default();
+        life.config.colour_cell = options.colour_cell;
+        life.config.speed = options.speed;
+        life
+    }
+
+    pub fn options(&self) -> LifeOptions {
+        LifeOptions {
+            colour_cell: self.config.colour_cell,
+            speed: self.config.speed,
+        }
+    }
+}
+
+impl Game for Life {
+    fn id(&self) -> &'static str {
+        "life"
+    }
+
+    fn name(&self) -> &'static str {
+        "Play the game of Life"
+    }
+
+    fn handle_input(&mut self, input: &dyn GameInput) {
+        let _ = self
+            .config
+            .handle_user_input(&InputWrapper::wrap(input), &self.cli);
+    }
+
+    fn tick(&mut self) {
+        self.config.update();
+    }
+
+    fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    fn scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
         ui.vertical(|ui| {
             ui.label("Reset game of life board:");
             if ui.add(egui::Button::new("Reset")).clicked() {
-                *self = Life::default();
+                self.reset();
             };
 
             ui.separator();
@@ -57,27 +115,29 @@ impl Life {
 
             ui.label("Speed of the game:");
             ui.add(egui::Slider::new(&mut self.config.speed, 0..=50));
+            self.scheduler
+                .set_step(Duration::from_secs(self.config.speed()));
         })
         .response
     }
 
-    pub fn ui(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        ctx.input(|i| {
-            let _ = self
-                .config
-                .handle_user_input(&InputWrapper { input: i }, &self.cli);
-        });
-
-        let two_seconds = Duration::from_secs(self.config.speed());
-        if self.time_check.elapsed() >= two_seconds {
-            self.config.update();
-            self.time_check = Instant::now();
-        }
+    fn buffer(&self) -> &WindowBuffer {
+        &self.config.window_buffer
+    }
 
-        ctx.request_repaint();
+    fn reset(&mut self) {
+        let clock = self.scheduler.clock();
+        *self = Life::default();
+        self.set_clock(clock);
+    }
 
-        egui::SidePanel::right("Configuration").show(ctx, |ui| self.configuration(ui));
+    fn load(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(options) = eframe::get_value(storage, self.id()) {
+            *self = Life::with_options(options);
+        }
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| draw_window_buffer(ui, &self.buffer));
+    fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, self.id(), &self.options());
     }
 }
@@ -0,0 +1,312 @@
+//! Translations of the resume content.
+//!
+//! A translation is a partial [`Cv`] stored in [`Cv::translations`]: every
+//! empty field, and every list item it doesn't have, is taken from the English
+//! content. List items are matched by position.
+
+use crate::cv::{
+    Cv, Education, Experience, Highlight, Identity, Labels, Language, Link, Project, SkillGroup,
+};
+
+/// Locale of the content written outside of [`Cv::translations`].
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Name of a locale in its own language, for the language selector.
+pub fn locale_name(locale: &str) -> &str {
+    match locale {
+        "en" => "English",
+        "fr" => "Français",
+        "it" => "Italiano",
+        "ja" => "日本語",
+        "de" => "Deutsch",
+        "es" => "Español",
+        other => other,
+    }
+}
+
+impl Cv {
+    /// Every locale the resume can be displayed in, English first.
+    pub fn locales(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_LOCALE)
+            .chain(
+                self.translations
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|locale| *locale != DEFAULT_LOCALE),
+            )
+            .collect()
+    }
+
+    /// The resume in `locale`, completed by the English content.
+    pub fn localized(&self, locale: &str) -> Cv {
+        let mut english = self.clone();
+        english.translations.clear();
+        match self.translations.get(locale) {
+            Some(translation) => {
+                let mut translation = translation.clone();
+                translation.translations.clear();
+                translation.or_english(&english)
+            }
+            None => english,
+        }
+    }
+}
+
+/// Completes a translated value with the English one.
+trait Fallback {
+    fn or_english(self, english: &Self) -> Self;
+}
+
+impl Fallback for String {
+    fn or_english(self, english: &Self) -> Self {
+        if self.is_empty() {
+            english.clone()
+        } else {
+            self
+        }
+    }
+}
+
+impl Fallback for Option<String> {
+    fn or_english(self, english: &Self) -> Self {
+        self.or_else(|| english.clone())
+    }
+}
+
+impl<T: Fallback + Clone> Fallback for Vec<T> {
+    fn or_english(self, english: &Self) -> Self {
+        if self.is_empty() {
+            return english.clone();
+        }
+        let mut translated: Vec<T> = self
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| match english.get(i) {
+                Some(english) => item.or_english(english),
+                None => item,
+            })
+            .collect();
+        if let Some(missing) = english.get(translated.len()..) {
+            translated.extend_from_slice(missing);
+        }
+        translated
+    }
+}
+
+impl Fallback for Cv {
+    fn or_english(self, english: &Self) -> Self {
+        Cv {
+            identity: self.identity.or_english(&english.identity),
+            summary: self.summary.or_english(&english.summary),
+            experience: self.experience.or_english(&english.experience),
+            education: self.education.or_english(&english.education),
+            projects: self.projects.or_english(&english.projects),
+            skills: self.skills.or_english(&english.skills),
+            languages: self.languages.or_english(&english.languages),
+            links: self.links.or_english(&english.links),
+            labels: self.labels.or_english(&english.labels),
+            translations: self.translations,
+        }
+    }
+}
+
+impl Fallback for Identity {
+    fn or_english(self, english: &Self) -> Self {
+        Identity {
+            name: self.name.or_english(&english.name),
+            headline: self.headline.or_english(&english.headline),
+        }
+    }
+}
+
+impl Fallback for Experience {
+    fn or_english(self, english: &Self) -> Self {
+        Experience {
+            company: self.company.or_english(&english.company),
+            position: self.position.or_english(&english.position),
+            start: self.start.or_english(&english.start),
+            end: self.end.or_english(&english.end),
+            summary: self.summary.or_english(&english.summary),
+        }
+    }
+}
+
+impl Fallback for Education {
+    fn or_english(self, english: &Self) -> Self {
+        Education {
+            institution: self.institution.or_english(&english.institution),
+            area: self.area.or_english(&english.area),
+            study_type: self.study_type.or_english(&english.study_type),
+            start: self.start.or_english(&english.start),
+            summary: self.summary.or_english(&english.summary),
+        }
+    }
+}
+
+impl Fallback for Project {
+    fn or_english(self, english: &Self) -> Self {
+        Project {
+            name: self.name.or_english(&english.name),
+            url: self.url.or_english(&english.url),
+            description: self.description.or_english(&english.description),
+            keywords: self.keywords.or_english(&english.keywords),
+            highlights: self.highlights.or_english(&english.highlights),
+        }
+    }
+}
+
+impl Fallback for Highlight {
+    fn or_english(self, english: &Self) -> Self {
+        Highlight {
+            name: self.name.or_english(&english.name),
+            description: self.description.or_english(&english.description),
+        }
+    }
+}
+
+impl Fallback for SkillGroup {
+    fn or_english(self, english: &Self) -> Self {
+        SkillGroup {
+            name: self.name.or_english(&english.name),
+            keywords: self.keywords.or_english(&english.keywords),
+        }
+    }
+}
+
+impl Fallback for Language {
+    fn or_english(self, english: &Self) -> Self {
+        Language {
+            language: self.language.or_english(&english.language),
+            fluency: self.fluency.or_english(&english.fluency),
+        }
+    }
+}
+
+impl Fallback for Link {
+    fn or_english(self, english: &Self) -> Self {
+        Link {
+            network: self.network.or_english(&english.network),
+            url: self.url.or_english(&english.url),
+        }
+    }
+}
+
+impl Fallback for Labels {
+    fn or_english(self, english: &Self) -> Self {
+        Labels {
+            at_a_glance: self.at_a_glance.or_english(&english.at_a_glance),
+            languages_spoken: self.languages_spoken.or_english(&english.languages_spoken),
+            social_media: self.social_media.or_english(&english.social_media),
+            greeting: self.greeting.or_english(&english.greeting),
+            education: self.education.or_english(&english.education),
+            projects: self.projects.or_english(&english.projects),
+            frameworks: self.frameworks.or_english(&english.frameworks),
+            summary_section: self.summary_section.or_english(&english.summary_section),
+            experience_section: self
+                .experience_section
+                .or_english(&english.experience_section),
+            education_section: self
+                .education_section
+                .or_english(&english.education_section),
+            projects_section: self.projects_section.or_english(&english.projects_section),
+            skills_section: self.skills_section.or_english(&english.skills_section),
+            position_at: self.position_at.or_english(&english.position_at),
+            since: self.since.or_english(&english.since),
+            until: self.until.or_english(&english.until),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cv() -> Cv {
+        let mut cv = Cv {
+            identity: Identity {
+                name: "Luna".to_string(),
+                headline: "Developer".to_string(),
+            },
+            summary: vec!["First".to_string(), "Second".to_string()],
+            ..Default::default()
+        };
+        for locale in ["ja", "fr", "en"] {
+            cv.translations.insert(locale.to_string(), Cv::default());
+        }
+        cv.translations.get_mut("fr").unwrap().identity.headline = "Développeuse".to_string();
+        cv.translations.get_mut("fr").unwrap().summary = vec!["Premier".to_string()];
+        cv.translations
+            .get_mut("fr")
+            .unwrap()
+            .labels
+            .experience_section = "Expérience".to_string();
+        cv
+    }
+
+    #[test]
+    fn locales_start_with_english() {
+        assert_eq!(cv().locales(), ["en", "fr", "ja"]);
+        assert_eq!(Cv::default().locales(), ["en"]);
+    }
+
+    #[test]
+    fn missing_translations_fall_back_to_english() {
+        let cv = cv();
+        let french = cv.localized("fr");
+        assert_eq!(french.identity.name, "Luna");
+        assert_eq!(french.identity.headline, "Développeuse");
+        assert_eq!(french.summary, ["Premier", "Second"]);
+        assert_eq!(french.labels.experience_section, "Expérience");
+        assert_eq!(french.labels.skills_section, "Skills");
+        assert!(french.translations.is_empty());
+    }
+
+    #[test]
+    fn unknown_locales_are_english() {
+        let cv = cv();
+        let english = Cv {
+            translations: Default::default(),
+            ..cv.clone()
+        };
+        assert_eq!(cv.localized("de"), english);
+        assert_eq!(cv.localized(DEFAULT_LOCALE), english);
+    }
+
+    #[test]
+    fn embedded_translations_have_every_label() {
+        let cv = Cv::embedded().unwrap();
+        for locale in cv.locales().into_iter().skip(1) {
+            let labels = &cv.translations[locale].labels;
+            assert!(!labels.experience_section.is_empty(), "{locale}");
+            assert_ne!(
+                labels.experience_section,
+                Labels::default().experience_section,
+                "{locale}"
+            );
+            assert!(!labels.position_at.is_empty(), "{locale}");
+            assert_ne!(
+                labels.position_at,
+                Labels::default().position_at,
+                "{locale}"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_translated_labels_are_the_resume_ones() {
+        let cv = Cv::from_toml(
+            r#"
+            [labels]
+            skills_section = "Toolbox"
+
+            [translations.fr.labels]
+            experience_section = "Expérience"
+            "#,
+        )
+        .unwrap();
+        let french = cv.localized("fr");
+        assert_eq!(french.labels.experience_section, "Expérience");
+        assert_eq!(french.labels.skills_section, "Toolbox");
+        assert_eq!(french.labels.education_section, "Education");
+    }
+}
@@ -6,6 +6,34 @@
 fn main() -> eframe::Result {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    // `cargo run -- --export-html dist/resume.html [resume.toml]` writes the
+    // resume as a static page instead of starting the app, `--export-pdf` as a PDF.
+    let args: Vec<_> = std::env::args_os().skip(1).collect();
+    if let Some(format @ ("--export-html" | "--export-pdf")) =
+        args.first().and_then(|arg| arg.to_str())
+    {
+        let Some(output) = args.get(1) else {
+            eprintln!("Usage: {format} <output file> [resume file]");
+            std::process::exit(1);
+        };
+        let cv = match args.get(2) {
+            Some(path) => resume::Cv::load(std::path::Path::new(path)),
+            None => resume::Cv::embedded(),
+        };
+        let result = cv.map_err(|e| e.to_string()).and_then(|cv| {
+            let content = match format {
+                "--export-html" => cv.to_html("en").into_bytes(),
+                _ => cv.to_pdf().map_err(|e| e.to_string())?,
+            };
+            std::fs::write(output, content).map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            eprintln!("Could not export the resume: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 300.0])
@@ -2,10 +2,14 @@ use egui::Ui;
 use maze::start_end_generator;
 use maze::{display, MazeConfig, Player};
 use rand::SeedableRng;
-use web_time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use web_time::Duration;
 use window_rs::WindowBuffer;
 
-use crate::{common::colour_changer, draw_window_buffer, InputWrapper};
+use crate::{
+    common::{colour_changer, GameInput, InputWrapper, Scheduler},
+    game::Game,
+};
 
 pub struct Maze {
     buffer: WindowBuffer,
@@ -13,10 +17,26 @@ pub struct Maze {
     pub config: MazeConfig,
     player: Player,
     start_point: (usize, usize),
-    update_time_wait: Instant,
+    scheduler: Scheduler,
     opened_walls: usize,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MazeOptions {
+    wall_color: u32,
+    path_color: u32,
+    player_color: u32,
+    finish_color: u32,
+    opened_walls: usize,
+}
+
+impl Default for MazeOptions {
+    fn default() -> Self {
+        Maze::default().options()
+    }
+}
+
 impl Default for Maze {
     fn default() -> Self {
         let mut buffer: WindowBuffer = WindowBuffer::new(30, 30);
@@ -43,18 +63,68 @@ impl Default for Maze {
             player,
             start_point,
             buffer,
-            update_time_wait: Instant::now(),
+            scheduler: Scheduler::new(Duration::from_millis(10)),
             opened_walls: 0,
         }
     }
 }
 
 impl Maze {
-    pub fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
+    pub fn with_options(options: MazeOptions) -> Self {
+        let mut maze = Maze::default();
+        maze.config.wall_color = options.wall_color;
+        maze.config.path_color = options.path_color;
+        maze.player.player_color = options.player_color;
+        maze.player.finish_color = options.finish_color;
+        maze.player.maze_config = maze.config.clone();
+        maze.opened_walls = options.opened_walls;
+        maze
+    }
+
+    pub fn options(&self) -> MazeOptions {
+        MazeOptions {
+            wall_color: self.config.wall_color,
+            path_color: self.config.path_color,
+            player_color: self.player.player_color,
+            finish_color: self.player.finish_color,
+            opened_walls: self.opened_walls,
+        }
+    }
+}
+
+impl Game for Maze {
+    fn id(&self) -> &'static str {
+        "maze"
+    }
+
+    fn name(&self) -> &'static str {
+        "Play a game of Maze"
+    }
+
+    fn handle_input(&mut self, input: &dyn GameInput) {
+        let _ = self
+            .player
+            .handle_user_input(&InputWrapper::wrap(input), &self.start_point);
+    }
+
+    fn tick(&mut self) {
+        display(&self.player, &mut self.buffer);
+        self.player.direction(&self.buffer);
+    }
+
+    fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    fn scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
         ui.vertical(|ui| {
             ui.label("Reset maze:");
             if ui.add(egui::Button::new("Reset")).clicked() {
-                *self = Maze::default();
+                self.reset();
             };
 
             ui.label("Wall:");
@@ -106,21 +176,23 @@ impl Maze {
         .response
     }
 
-    pub fn ui(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let elapsed_time = Duration::from_millis(10_u64);
-        ctx.input(|i| {
-            let _ = self
-                .player
-                .handle_user_input(&InputWrapper { input: i }, &self.start_point);
-        });
+    fn buffer(&self) -> &WindowBuffer {
+        &self.buffer
+    }
 
-        if self.update_time_wait.elapsed() >= elapsed_time {
-            display(&self.player, &mut self.buffer);
-            self.player.direction(&self.buffer);
-            self.update_time_wait = Instant::now();
+    fn reset(&mut self) {
+        let clock = self.scheduler.clock();
+        *self = Maze::default();
+        self.set_clock(clock);
+    }
+
+    fn load(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(options) = eframe::get_value(storage, self.id()) {
+            *self = Maze::with_options(options);
         }
-        egui::SidePanel::right("Configuration").show(ctx, |ui| self.configuration(ui));
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| draw_window_buffer(ui, &self.buffer));
+    fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, self.id(), &self.options());
     }
 }
@@ -0,0 +1,406 @@
+//! Paginated PDF rendering of a [`Cv`], laid out like the resume page: the
+//! "At a glance" column on the left and the narrative on the right.
+
+use std::fmt;
+
+use printpdf::{
+    Actions, BorderArray, BuiltinFont, Color, IndirectFontRef, LinkAnnotation, Mm, PdfDocument,
+    PdfDocumentReference, PdfLayerReference, Rect, Rgb,
+};
+
+use crate::cv::{spans, Cv};
+
+/// Why the resume couldn't be rendered as a PDF.
+#[derive(Debug)]
+pub enum PdfError {
+    /// The built-in PDF fonts only cover the Windows-1252 characters, the
+    /// others are listed once each.
+    UnsupportedCharacters(Vec<char>),
+    Pdf(printpdf::Error),
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdfError::UnsupportedCharacters(chars) => write!(
+                f,
+                "the PDF fonts can't display {}, export another language",
+                chars.iter().take(10).collect::<String>()
+            ),
+            PdfError::Pdf(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<printpdf::Error> for PdfError {
+    fn from(e: printpdf::Error) -> Self {
+        PdfError::Pdf(e)
+    }
+}
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const SIDEBAR: Column = Column {
+    x: MARGIN,
+    width: 50.0,
+};
+const MAIN: Column = Column {
+    x: 75.0,
+    width: PAGE_WIDTH - 75.0 - MARGIN,
+};
+
+const HEADING_SIZE: f32 = 14.0;
+const TITLE_SIZE: f32 = 20.0;
+const BODY_SIZE: f32 = 10.0;
+const PT_TO_MM: f32 = 0.3528;
+
+#[derive(Clone, Copy)]
+struct Column {
+    x: f32,
+    width: f32,
+}
+
+impl Cv {
+    /// Renders the resume as a PDF document using the built-in PDF fonts,
+    /// which can't display non-Latin scripts such as Japanese.
+    pub fn to_pdf(&self) -> Result<Vec<u8>, PdfError> {
+        let labels = &self.labels;
+        let mut pdf = PdfWriter::new(&self.identity.name)?;
+
+        pdf.heading(SIDEBAR, &labels.at_a_glance);
+        pdf.bold(
+            SIDEBAR,
+            &format!("{}:", labels.languages_spoken.to_uppercase()),
+            BODY_SIZE,
+        );
+        for language in &self.languages {
+            pdf.rich(
+                SIDEBAR,
+                &format!("{} ({})", language.language, language.fluency),
+            );
+        }
+        for group in &self.skills {
+            pdf.skip(BODY_SIZE);
+            pdf.bold(
+                SIDEBAR,
+                &format!("{}:", group.name.to_uppercase()),
+                BODY_SIZE,
+            );
+            for keyword in &group.keywords {
+                pdf.rich(SIDEBAR, keyword);
+            }
+        }
+        pdf.skip(BODY_SIZE);
+        pdf.bold(
+            SIDEBAR,
+            &format!("{}:", labels.social_media.to_uppercase()),
+            BODY_SIZE,
+        );
+        for link in &self.links {
+            pdf.link(SIDEBAR, &link.network, &link.url);
+        }
+
+        pdf.rewind();
+        pdf.bold(MAIN, &self.identity.name, TITLE_SIZE);
+        pdf.rich(MAIN, &self.identity.headline);
+        for paragraph in &self.summary {
+            pdf.skip(BODY_SIZE);
+            pdf.rich(MAIN, paragraph);
+        }
+
+        pdf.heading(MAIN, &labels.experience_section);
+        for experience in &self.experience {
+            pdf.bold(MAIN, &labels.position_at(experience), BODY_SIZE);
+            pdf.rich(MAIN, &experience.summary);
+            pdf.skip(BODY_SIZE);
+        }
+
+        pdf.heading(MAIN, &labels.education_section);
+        for education in &self.education {
+            pdf.bold(
+                MAIN,
+                &format!("{}, {}", education.study_type, education.institution),
+                BODY_SIZE,
+            );
+            pdf.rich(MAIN, &education.summary);
+            pdf.skip(BODY_SIZE);
+        }
+
+        pdf.heading(MAIN, &labels.projects_section);
+        for project in &self.projects {
+            match &project.url {
+                Some(url) => pdf.link(MAIN, &project.name, url),
+                None => pdf.bold(MAIN, &project.name, BODY_SIZE),
+            }
+            if !project.description.is_empty() {
+                pdf.rich(MAIN, &project.description);
+            }
+            if !project.keywords.is_empty() {
+                pdf.rich(
+                    MAIN,
+                    &format!("**{}:** {}", labels.frameworks, project.keywords.join(", ")),
+                );
+            }
+            for highlight in &project.highlights {
+                if highlight.name.is_empty() {
+                    pdf.rich(MAIN, &format!("- {}", highlight.description));
+                } else {
+                    pdf.rich(
+                        MAIN,
+                        &format!("- **{}:** {}", highlight.name, highlight.description),
+                    );
+                }
+            }
+            pdf.skip(BODY_SIZE);
+        }
+
+        if !pdf.unsupported.is_empty() {
+            return Err(PdfError::UnsupportedCharacters(pdf.unsupported));
+        }
+        Ok(pdf.doc.save_to_bytes()?)
+    }
+}
+
+/// Whether `c` is in the Windows-1252 encoding of the built-in PDF fonts.
+fn is_supported(c: char) -> bool {
+    const WINDOWS_1252: &str = "€‚ƒ„…†‡ˆ‰Š‹ŒŽ‘’“”•–—˜™š›œžŸ";
+    matches!(c, ' '..='~' | '\u{a0}'..='ÿ') || c.is_whitespace() || WINDOWS_1252.contains(c)
+}
+
+/// Writes text top to bottom, adding pages as needed.
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    pages: Vec<PdfLayerReference>,
+    /// Index in `pages` of the page being written.
+    page: usize,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    /// Position of the next line from the bottom of the page, in mm.
+    y: f32,
+    /// The characters written that the fonts can't display.
+    unsupported: Vec<char>,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, printpdf::Error> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Resume");
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+        let first_page = doc.get_page(page).get_layer(layer);
+        Ok(Self {
+            doc,
+            pages: vec![first_page],
+            page: 0,
+            regular,
+            bold,
+            y: PAGE_HEIGHT - MARGIN,
+            unsupported: Vec::new(),
+        })
+    }
+
+    fn check(&mut self, text: &str) {
+        for c in text.chars() {
+            if !is_supported(c) && !self.unsupported.contains(&c) {
+                self.unsupported.push(c);
+            }
+        }
+    }
+
+    /// Goes back to the top of the first page, to fill another column.
+    fn rewind(&mut self) {
+        self.page = 0;
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Moves to the next line, changing page when there is no room left.
+    fn line(&mut self, size: f32) -> f32 {
+        let height = size * PT_TO_MM * 1.4;
+        if self.y - height < MARGIN {
+            self.page += 1;
+            if self.page == self.pages.len() {
+                let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Resume");
+                self.pages.push(self.doc.get_page(page).get_layer(layer));
+            }
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= height;
+        self.y
+    }
+
+    fn skip(&mut self, size: f32) {
+        self.line(size);
+    }
+
+    fn heading(&mut self, column: Column, text: &str) {
+        self.skip(BODY_SIZE);
+        self.bold(column, text, HEADING_SIZE);
+        self.skip(BODY_SIZE / 2.0);
+    }
+
+    fn bold(&mut self, column: Column, text: &str, size: f32) {
+        self.check(text);
+        for line in wrap(&[(text, true)], column.width, size) {
+            let y = self.line(size);
+            self.runs(column.x, y, &line, size);
+        }
+    }
+
+    /// Writes a text whose `**strong**` spans are in bold.
+    fn rich(&mut self, column: Column, text: &str) {
+        self.check(text);
+        let spans: Vec<_> = spans(text).collect();
+        for line in wrap(&spans, column.width, BODY_SIZE) {
+            let y = self.line(BODY_SIZE);
+            self.runs(column.x, y, &line, BODY_SIZE);
+        }
+    }
+
+    fn link(&mut self, column: Column, text: &str, url: &str) {
+        self.check(text);
+        let blue = Color::Rgb(Rgb::new(0.0, 0.2, 0.8, None));
+        let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+        for line in wrap(&[(text, false)], column.width, BODY_SIZE) {
+            let y = self.line(BODY_SIZE);
+            let layer = &self.pages[self.page];
+            layer.set_fill_color(blue.clone());
+            self.runs(column.x, y, &line, BODY_SIZE);
+            layer.set_fill_color(black.clone());
+
+            let width: f32 = line
+                .iter()
+                .map(|(run, bold)| width(run, *bold, BODY_SIZE))
+                .sum();
+            layer.add_link_annotation(LinkAnnotation::new(
+                Rect::new(
+                    Mm(column.x),
+                    Mm(y - 1.0),
+                    Mm(column.x + width),
+                    Mm(y + BODY_SIZE * PT_TO_MM),
+                ),
+                Some(BorderArray::Solid([0.0, 0.0, 0.0])),
+                None,
+                Actions::uri(url.to_string()),
+                None,
+            ));
+        }
+    }
+
+    /// Writes a line made of regular and bold runs.
+    fn runs(&self, mut x: f32, y: f32, line: &[(String, bool)], size: f32) {
+        let layer = &self.pages[self.page];
+        for (run, bold) in line {
+            let font = if *bold { &self.bold } else { &self.regular };
+            layer.use_text(run.as_str(), size, Mm(x), Mm(y), font);
+            x += width(run, *bold, size);
+        }
+    }
+}
+
+/// Approximate width in mm of `text` in Helvetica, the built-in fonts don't
+/// come with their metrics.
+fn width(text: &str, bold: bool, size: f32) -> f32 {
+    let em = if bold { 0.55 } else { 0.5 };
+    text.chars().count() as f32 * size * em * PT_TO_MM
+}
+
+/// Splits `(text, bold)` spans into lines fitting in `max_width` mm, each line
+/// being a list of runs sharing the same weight. Lines only break where the
+/// text has whitespace, a word and the punctuation after its span stay together.
+fn wrap(spans: &[(&str, bool)], max_width: f32, size: f32) -> Vec<Vec<(String, bool)>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<(String, bool)> = Vec::new();
+    let mut line_width = 0.0;
+    // Whether there was whitespace since the last word.
+    let mut space = false;
+
+    for &(text, bold) in spans {
+        let mut rest = text;
+        loop {
+            let trimmed = rest.trim_start();
+            space |= trimmed.len() < rest.len();
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+            if end == 0 {
+                break;
+            }
+            let (word, after) = trimmed.split_at(end);
+            rest = after;
+
+            let gap = space && !line.is_empty();
+            let word_width =
+                width(word, bold, size) + if gap { width(" ", bold, size) } else { 0.0 };
+            if gap && line_width + word_width > max_width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+            let word = if space && !line.is_empty() {
+                format!(" {word}")
+            } else {
+                word.to_string()
+            };
+            line_width += width(&word, bold, size);
+            match line.last_mut() {
+                Some((run, run_bold)) if *run_bold == bold => run.push_str(&word),
+                _ => line.push((word, bold)),
+            }
+            space = false;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latin_locales_are_rendered() {
+        let cv = Cv::embedded().unwrap();
+        for locale in ["en", "fr", "it"] {
+            let pdf = cv.localized(locale).to_pdf().unwrap();
+            assert!(pdf.starts_with(b"%PDF"), "{locale}");
+        }
+    }
+
+    #[test]
+    fn lines_break_at_whitespace_only() {
+        let spans = [
+            ("I worked at ", false),
+            ("Ubisoft", true),
+            (". Then", false),
+        ];
+        let lines = wrap(&spans, 1000.0, 10.0);
+        let runs: Vec<_> = lines[0]
+            .iter()
+            .map(|(run, bold)| (run.as_str(), *bold))
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                ("I worked at", false),
+                (" Ubisoft", true),
+                (". Then", false)
+            ]
+        );
+
+        let narrow = wrap(&spans, width("I worked at", false, 10.0), 10.0);
+        assert_eq!(narrow.len(), 3);
+        assert_eq!(
+            narrow[1],
+            [("Ubisoft".to_string(), true), (".".to_string(), false)]
+        );
+        assert_eq!(narrow[2], [("Then".to_string(), false)]);
+    }
+
+    #[test]
+    fn japanese_is_refused() {
+        let cv = Cv::embedded().unwrap();
+        match cv.localized("ja").to_pdf() {
+            Err(PdfError::UnsupportedCharacters(chars)) => assert!(chars.contains(&'概')),
+            other => panic!("{:?}", other.map(|_| ())),
+        }
+    }
+}
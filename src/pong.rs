@@ -1,18 +1,46 @@
-use crate::{common::colour_changer, draw_window_buffer, InputWrapper};
+use crate::{
+    common::{colour_changer, Clock, GameInput, InputWrapper, Scheduler},
+    game::Game,
+};
 use egui::Ui;
 use pong::{creation_pongs, display, Cli, Difficulty, World};
 use rand::SeedableRng;
-use web_time::Instant;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+use web_time::{Duration, Instant};
 use window_rs::WindowBuffer;
 
 pub struct Pong {
     buffer: WindowBuffer,
     config: World,
+    /// When the ball and the paddles last moved, on the clock of `scheduler`.
     instant_ball: Instant,
     instant_pong: Instant,
+    /// The pong crate times the ball and the paddles itself with `instant_ball`
+    /// and `instant_pong`, the scheduler ticks as often as the fastest of them.
+    scheduler: Scheduler,
+    /// When the game was paused, its timers are frozen until it resumes.
+    paused_at: Option<Instant>,
     cli: Cli,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PongOptions {
+    ball_speed: usize,
+    pong_speed: usize,
+    number_of_points_to_reach: usize,
+    player_1_colour: u32,
+    player_2_colour: u32,
+    ball_colour: u32,
+}
+
+impl Default for PongOptions {
+    fn default() -> Self {
+        Pong::default().options()
+    }
+}
+
 impl Default for Pong {
     fn default() -> Self {
         let cli = Cli {
@@ -45,22 +73,105 @@ impl Default for Pong {
             0xFFFFFF00,
         );
 
+        let scheduler = Scheduler::new(tick_step(&cli));
         Self {
             buffer,
             config,
-            instant_ball: Instant::now(),
-            instant_pong: Instant::now(),
+            instant_ball: scheduler.now(),
+            instant_pong: scheduler.now(),
+            scheduler,
+            paused_at: None,
             cli,
         }
     }
 }
 
 impl Pong {
-    pub fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
+    pub fn with_options(options: PongOptions) -> Self {
+        let mut pong = Pong::default();
+        pong.cli.ball_speed = options.ball_speed;
+        pong.cli.pong_speed = options.pong_speed;
+        pong.cli.number_of_points_to_reach = options.number_of_points_to_reach;
+        pong.config.player_1_colour = options.player_1_colour;
+        pong.config.player_2_colour = options.player_2_colour;
+        pong.config.ball_colour = options.ball_colour;
+        pong
+    }
+
+    pub fn options(&self) -> PongOptions {
+        PongOptions {
+            ball_speed: self.cli.ball_speed,
+            pong_speed: self.cli.pong_speed,
+            number_of_points_to_reach: self.cli.number_of_points_to_reach,
+            player_1_colour: self.config.player_1_colour,
+            player_2_colour: self.config.player_2_colour,
+            ball_colour: self.config.ball_colour,
+        }
+    }
+}
+
+impl Game for Pong {
+    fn id(&self) -> &'static str {
+        "pong"
+    }
+
+    fn name(&self) -> &'static str {
+        "Play a game of Pong"
+    }
+
+    fn handle_input(&mut self, input: &dyn GameInput) {
+        // The paddles are created first, so there is something to move.
+        if self.config.player_1_pong.is_empty() {
+            creation_pongs(&mut self.config, &self.buffer)
+        }
+
+        let _ = self
+            .config
+            .handle_user_input(&InputWrapper::wrap(input), &self.buffer);
+    }
+
+    fn tick(&mut self) {
+        // The pong crate compares its timers with the wall clock, they are
+        // moved to it for the update and back to the clock of the scheduler.
+        let now = self.scheduler.now();
+        let wall = Instant::now();
+        let mut instant_pong = shift(self.instant_pong, now, wall);
+        let mut instant_ball = shift(self.instant_ball, now, wall);
+        self.config.update(
+            &mut self.buffer,
+            &self.cli,
+            &mut instant_pong,
+            &mut instant_ball,
+        );
+        self.instant_pong = shift(instant_pong, wall, now);
+        self.instant_ball = shift(instant_ball, wall, now);
+        display(&self.config, &mut self.buffer);
+    }
+
+    fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    fn scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    /// Restarts the timers of the ball and the paddles on `clock`.
+    fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        let now = clock.now();
+        self.scheduler.set_clock(clock);
+        self.instant_ball = now;
+        self.instant_pong = now;
+        if let Some(paused_at) = &mut self.paused_at {
+            *paused_at = now;
+        }
+    }
+
+    fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
         ui.vertical(|ui| {
             ui.label("Reset pong board:");
             if ui.add(egui::Button::new("Reset")).clicked() {
-                *self = Pong::default();
+                self.reset();
             };
 
             ui.separator();
@@ -72,6 +183,7 @@ impl Pong {
 
             ui.label("Pong speed:");
             ui.add(egui::DragValue::new(&mut self.cli.pong_speed).speed(1));
+            self.scheduler.set_step(tick_step(&self.cli));
 
             ui.separator();
 
@@ -99,28 +211,68 @@ impl Pong {
         .response
     }
 
-    pub fn ui(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if self.config.player_1_pong.is_empty() {
-            creation_pongs(&mut self.config, &self.buffer)
+    fn buffer(&self) -> &WindowBuffer {
+        &self.buffer
+    }
+
+    fn reset(&mut self) {
+        let clock = self.scheduler.clock();
+        *self = Pong::default();
+        self.set_clock(clock);
+    }
+
+    fn pause(&mut self) {
+        self.scheduler.pause();
+        let now = self.scheduler.now();
+        self.paused_at.get_or_insert(now);
+    }
+
+    fn resume(&mut self) {
+        self.scheduler.resume();
+        if let Some(paused_at) = self.paused_at.take() {
+            let paused_for = self.scheduler.now() - paused_at;
+            self.instant_ball += paused_for;
+            self.instant_pong += paused_for;
         }
+    }
 
-        ctx.input(|i| {
-            let _ = self
-                .config
-                .handle_user_input(&InputWrapper { input: i }, &self.buffer);
-        });
+    fn load(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(options) = eframe::get_value(storage, self.id()) {
+            *self = Pong::with_options(options);
+        }
+    }
 
-        self.config.update(
-            &mut self.buffer,
-            &self.cli,
-            &mut self.instant_pong,
-            &mut self.instant_ball,
-        );
-        display(&self.config, &mut self.buffer);
-        ctx.request_repaint();
+    fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, self.id(), &self.options());
+    }
+}
+
+/// Duration of a tick, in milliseconds like the speeds of `cli`.
+fn tick_step(cli: &Cli) -> Duration {
+    Duration::from_millis(cli.ball_speed.min(cli.pong_speed) as u64)
+}
+
+/// The time of `instant` on the clock where `from` is `to`.
+fn shift(instant: Instant, from: Instant, to: Instant) -> Instant {
+    if instant <= from {
+        to.checked_sub(from - instant).unwrap_or(to)
+    } else {
+        to + (instant - from)
+    }
+}
 
-        egui::SidePanel::right("Configuration").show(ctx, |ui| self.configuration(ui));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        egui::CentralPanel::default().show(ctx, |ui| draw_window_buffer(ui, &self.buffer));
+    #[test]
+    fn timers_keep_their_age_across_clocks() {
+        let wall = Instant::now();
+        let clock = wall + Duration::from_secs(60);
+        let moved = clock - Duration::from_millis(15);
+        assert_eq!(shift(moved, clock, wall), wall - Duration::from_millis(15));
+        assert_eq!(shift(shift(moved, clock, wall), wall, clock), moved);
+        let reset = wall + Duration::from_millis(2);
+        assert_eq!(shift(reset, wall, clock), clock + Duration::from_millis(2));
     }
 }
@@ -0,0 +1,225 @@
+//! Snake games recorded tick by tick, saved to a file and watched again.
+//!
+//! The snake crate draws the food from its own random generator, so instead
+//! of a seed the file keeps the starting board and every food drawn.
+
+use serde::{Deserialize, Serialize};
+use snake::Direction;
+
+use crate::snake::SnakeOptions;
+
+/// Extension of the replay files, also used to tell them from dropped resumes.
+pub const EXTENSION: &str = "snakereplay";
+const VERSION: u32 = 1;
+/// Most ticks a replay file may hold, eight hours at the default speed.
+const MAX_TICKS: usize = 1_000_000;
+
+/// The snakes and the food when the board was filled.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Start {
+    pub snake: Vec<(usize, usize)>,
+    pub second_snake: Option<Vec<(usize, usize)>>,
+    pub food: (usize, usize),
+}
+
+/// The directions of both snakes during a tick, and the food after it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Tick {
+    directions: u8,
+    pub food: (usize, usize),
+}
+
+impl Tick {
+    pub fn new(first: &Direction, second: &Direction, food: (usize, usize)) -> Self {
+        Self {
+            directions: direction_code(first) | direction_code(second) << 4,
+            food,
+        }
+    }
+
+    pub fn first_direction(&self) -> Direction {
+        direction_from_code(self.directions & 0xF)
+    }
+
+    pub fn second_direction(&self) -> Direction {
+        direction_from_code(self.directions >> 4)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Recording {
+    pub start: Start,
+    pub ticks: Vec<Tick>,
+}
+
+/// What is written to a replay file, the directions as runs of equal ticks
+/// and the food only when it moved.
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+    version: u32,
+    options: SnakeOptions,
+    start: Start,
+    /// Number of ticks and their directions.
+    moves: Vec<(usize, u8)>,
+    /// Tick after which the food moved, and where to.
+    food: Vec<(usize, (usize, usize))>,
+}
+
+impl Recording {
+    pub fn to_bytes(&self, options: &SnakeOptions) -> Vec<u8> {
+        let mut moves: Vec<(usize, u8)> = Vec::new();
+        let mut food = Vec::new();
+        let mut last_food = self.start.food;
+        for (i, tick) in self.ticks.iter().enumerate() {
+            match moves.last_mut() {
+                Some((count, directions)) if *directions == tick.directions => *count += 1,
+                _ => moves.push((1, tick.directions)),
+            }
+            if tick.food != last_food {
+                food.push((i, tick.food));
+                last_food = tick.food;
+            }
+        }
+        let file = ReplayFile {
+            version: VERSION,
+            options: options.clone(),
+            start: self.start.clone(),
+            moves,
+            food,
+        };
+        serde_json::to_vec(&file).expect("replays serialize to JSON")
+    }
+
+    /// The options the game was played with and its recording.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(SnakeOptions, Recording), String> {
+        let file: ReplayFile = serde_json::from_slice(bytes).map_err(|e| e.to_string())?;
+        if file.version != VERSION {
+            return Err(format!("Unsupported replay version {}", file.version));
+        }
+        let length = file
+            .moves
+            .iter()
+            .try_fold(0usize, |length, (count, _)| length.checked_add(*count))
+            .filter(|length| *length <= MAX_TICKS)
+            .ok_or(format!("Replays are limited to {MAX_TICKS} ticks"))?;
+
+        let mut changes = file.food.into_iter().peekable();
+        let mut food = file.start.food;
+        let mut ticks = Vec::with_capacity(length);
+        for (count, directions) in file.moves {
+            for _ in 0..count {
+                if let Some((_, moved)) = changes.next_if(|(tick, _)| *tick == ticks.len()) {
+                    food = moved;
+                }
+                ticks.push(Tick { directions, food });
+            }
+        }
+        let recording = Recording {
+            start: file.start,
+            ticks,
+        };
+        Ok((file.options, recording))
+    }
+}
+
+/// A recording being watched.
+pub struct Playback {
+    pub recording: Recording,
+    /// Number of ticks played.
+    pub tick: usize,
+    pub paused: bool,
+    /// How many times faster than the recorded game.
+    pub speed: f32,
+}
+
+impl Playback {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            tick: 0,
+            paused: false,
+            speed: 1.0,
+        }
+    }
+
+    /// The next tick to play, `None` at the end of the recording.
+    pub fn next(&mut self) -> Option<Tick> {
+        let tick = *self.recording.ticks.get(self.tick)?;
+        self.tick += 1;
+        Some(tick)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.tick >= self.recording.ticks.len()
+    }
+}
+
+/// Whether a dropped file named `name` is a replay.
+pub fn is_replay(name: &str) -> bool {
+    name.ends_with(&format!(".{EXTENSION}"))
+}
+
+fn direction_code(direction: &Direction) -> u8 {
+    match direction {
+        Direction::Still => 0,
+        Direction::North => 1,
+        Direction::South => 2,
+        Direction::East => 3,
+        Direction::West => 4,
+    }
+}
+
+fn direction_from_code(code: u8) -> Direction {
+    match code {
+        1 => Direction::North,
+        2 => Direction::South,
+        3 => Direction::East,
+        4 => Direction::West,
+        _ => Direction::Still,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recordings_round_trip() {
+        let recording = Recording {
+            start: Start {
+                snake: vec![(3, 2), (2, 2)],
+                second_snake: None,
+                food: (7, 7),
+            },
+            ticks: vec![
+                Tick::new(&Direction::East, &Direction::Still, (7, 7)),
+                Tick::new(&Direction::East, &Direction::Still, (7, 7)),
+                Tick::new(&Direction::South, &Direction::North, (1, 4)),
+                Tick::new(&Direction::South, &Direction::North, (1, 4)),
+                Tick::new(&Direction::West, &Direction::North, (5, 0)),
+            ],
+        };
+        let bytes = recording.to_bytes(&SnakeOptions::default());
+        let (_, read) = Recording::from_bytes(&bytes).unwrap();
+        assert!(read.ticks == recording.ticks);
+        assert_eq!(read.start.snake, recording.start.snake);
+        assert_eq!(read.start.food, recording.start.food);
+        let last = read.ticks[4];
+        assert_eq!(direction_code(&last.first_direction()), 4);
+        assert_eq!(direction_code(&last.second_direction()), 1);
+    }
+
+    #[test]
+    fn long_replays_are_refused() {
+        let file = |moves: &str| {
+            format!(
+                r#"{{"version":1,"options":{{}},"start":{{"snake":[],"second_snake":null,"food":[0,0]}},"moves":{moves},"food":[]}}"#
+            )
+        };
+        assert!(Recording::from_bytes(file("[[3,1]]").as_bytes()).is_ok());
+        let too_long = format!("[[{MAX_TICKS},1],[1,1]]");
+        assert!(Recording::from_bytes(file(&too_long).as_bytes()).is_err());
+        let overflow = format!("[[{},1],[{},1]]", usize::MAX, usize::MAX);
+        assert!(Recording::from_bytes(file(&overflow).as_bytes()).is_err());
+    }
+}
@@ -1,56 +1,395 @@
-use crate::{cv::Cv, life::Life, maze::Maze, pong::Pong, snake::Snake};
+use std::path::PathBuf;
+
+use crate::{
+    common::{save_file, BoardLayout},
+    cv::{Cv, LoadError},
+    fonts::{install_fonts, missing_glyphs},
+    game::{registry, Game},
+    gamepad::Gamepads,
+    keymap::Keymap,
+    locale::{locale_name, DEFAULT_LOCALE},
+    replay::is_replay,
+    touch::TouchControls,
+};
+
+const LOCALE_KEY: &str = "locale";
+const VIEW_KEY: &str = "view";
+const DARK_MODE_KEY: &str = "dark_mode";
+const KEYMAP_KEY: &str = "keymap";
+const GAMEPAD_PLAYERS_KEY: &str = "gamepad_players";
+/// Ids of the views that aren't games, next to the ids of the games.
+const RESUME_ID: &str = "resume";
+const CONTROLS_ID: &str = "controls";
 
 #[derive(Default)]
 pub struct Resume {
     viewing: View,
-    #[allow(dead_code)]
+    /// The view of the last frame, its game is paused when `viewing` changes.
+    shown: View,
+    /// The resume as loaded, with all its translations.
     cv: Cv,
-    snake: Snake,
-    pong: Pong,
-    maze: Maze,
-    life: Life,
+    locale: String,
+    /// `cv` translated in `locale`, the one displayed and exported.
+    localized: Cv,
+    /// Why the last resume file couldn't be displayed.
+    cv_error: Option<LoadError>,
+    /// File the resume was loaded from, so it can be reloaded after an edit.
+    cv_path: Option<PathBuf>,
+    /// Where "Export PDF" writes, the name of the downloaded file on the web.
+    pdf_path: String,
+    /// Column at which the text and Markdown exports wrap, `0` to not wrap.
+    text_width: usize,
+    /// Outcome of the last export, shown in the top bar.
+    export_status: Option<String>,
+    /// Whether `localized` changed since its glyphs were checked against the fonts.
+    check_glyphs: bool,
+    /// Theme chosen in the top bar, saved as egui doesn't persist it.
+    dark_mode: bool,
+    /// Last URL hash followed, the view is only changed when it changes.
+    #[cfg(target_arch = "wasm32")]
+    hash: String,
+    /// The games of the menu bar, [`View::Game`] indexes them.
+    games: Vec<Box<dyn Game>>,
+    /// The keyboard keys of the game actions, shared by all the games.
+    keymap: Keymap,
+    /// Index in [`crate::keymap::ACTIONS`] of the action waiting for a key in the controls view.
+    rebinding: Option<usize>,
+    /// The game controllers, `None` when they can't be read.
+    gamepads: Option<Gamepads>,
+    /// The D-pad and buttons shown over the games on touch screens.
+    touch: TouchControls,
 }
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 enum View {
     #[default]
     Resume,
-    Snake,
-    Pong,
-    Maze,
-    Life,
+    Game(usize),
+    Controls,
 }
 
 impl Resume {
     /// Called once before the first frame.
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        Resume {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        install_fonts(&cc.egui_ctx);
+
+        let storage = cc.storage;
+        let locale = storage
+            .and_then(|storage| eframe::get_value(storage, LOCALE_KEY))
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+        let dark_mode = storage
+            .and_then(|storage| eframe::get_value(storage, DARK_MODE_KEY))
+            .unwrap_or_else(|| cc.egui_ctx.style().visuals.dark_mode);
+        cc.egui_ctx.set_visuals(if dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        });
+
+        let mut resume = Resume {
             viewing: View::default(),
+            shown: View::default(),
             cv: Cv::default(),
-            maze: Maze::default(),
-            pong: Pong::default(),
-            snake: Snake::default(),
-            life: Life::default(),
+            locale,
+            localized: Cv::default(),
+            cv_error: None,
+            cv_path: None,
+            pdf_path: "resume.pdf".to_string(),
+            text_width: 80,
+            export_status: None,
+            check_glyphs: false,
+            dark_mode,
+            #[cfg(target_arch = "wasm32")]
+            hash: String::new(),
+            games: registry(),
+            keymap: storage
+                .and_then(|storage| eframe::get_value(storage, KEYMAP_KEY))
+                .unwrap_or_default(),
+            rebinding: None,
+            gamepads: Gamepads::native(),
+            touch: TouchControls::default(),
+        };
+        if let Some(storage) = storage {
+            for game in &mut resume.games {
+                game.load(storage);
+            }
+            if let Some(gamepads) = &mut resume.gamepads {
+                gamepads.players =
+                    eframe::get_value(storage, GAMEPAD_PLAYERS_KEY).unwrap_or_default();
+            }
+            let view: Option<String> = eframe::get_value(storage, VIEW_KEY);
+            if let Some(view) = view.and_then(|id| resume.view_from_id(&id)) {
+                resume.viewing = view;
+            }
+        }
+        resume.set_cv(Cv::embedded());
+
+        // On the web, the URL hash picks the view and follows it: `#snake`, `#life`...
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::common::repaint_on_hash_change(&cc.egui_ctx);
+            resume.follow_hash();
+        }
+
+        // A resume file can be given on the command line: `cargo run -- my_resume.toml`
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = std::env::args_os().nth(1) {
+            resume.cv_path = Some(path.into());
+            resume.reload_cv();
         }
+
+        resume
     }
 
-    pub fn ui(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+    /// Displays `cv` if it loaded, otherwise keeps the current one and reports the error.
+    fn set_cv(&mut self, cv: Result<Cv, LoadError>) {
+        match cv {
+            Ok(cv) => {
+                self.cv = cv;
+                self.cv_error = None;
+                self.localize();
+            }
+            Err(e) => {
+                log::error!("Could not load the resume: {e}");
+                self.cv_error = Some(e);
+            }
+        }
+    }
+
+    fn localize(&mut self) {
+        self.localized = self.cv.localized(&self.locale);
+        self.check_glyphs = true;
+    }
+
+    /// Reports the characters of the displayed resume that would show as tofu.
+    fn check_glyphs(&mut self, ctx: &egui::Context) {
+        if !std::mem::take(&mut self.check_glyphs) {
+            return;
+        }
+        let missing = missing_glyphs(
+            ctx,
+            egui::FontFamily::Proportional,
+            &self.localized.to_text(0),
+        );
+        if !missing.is_empty() {
+            let missing: String = missing.into_iter().collect();
+            log::warn!("No font can display these characters of the resume: {missing}");
+        }
+    }
+
+    fn reload_cv(&mut self) {
+        if let Some(path) = &self.cv_path {
+            let cv = Cv::load(path);
+            self.set_cv(cv);
+        }
+    }
+
+    fn export_pdf(&self) -> String {
+        let result = self
+            .localized
+            .to_pdf()
+            .map_err(|e| e.to_string())
+            .and_then(|pdf| save_file(&self.pdf_path, "application/pdf", &pdf));
+        match result {
+            Ok(()) => format!("Saved {}", self.pdf_path),
+            Err(e) => format!("Could not export the PDF: {e}"),
+        }
+    }
+
+    /// Identifies `view` in the URL hash and the app storage.
+    fn view_id(&self, view: View) -> &'static str {
+        match view {
+            View::Resume => RESUME_ID,
+            View::Game(index) => self.games[index].id(),
+            View::Controls => CONTROLS_ID,
+        }
+    }
+
+    fn view_from_id(&self, id: &str) -> Option<View> {
+        match id {
+            RESUME_ID => return Some(View::Resume),
+            CONTROLS_ID => return Some(View::Controls),
+            _ => {}
+        }
+        self.games
+            .iter()
+            .position(|game| game.id() == id)
+            .map(View::Game)
+    }
+
+    /// Shows the view named by the URL hash when it changed since the last frame.
+    #[cfg(target_arch = "wasm32")]
+    fn follow_hash(&mut self) {
+        let hash = crate::common::location_hash();
+        if hash != self.hash {
+            if let Some(view) = hash.strip_prefix('#').and_then(|id| self.view_from_id(id)) {
+                self.viewing = view;
+            }
+            self.hash = hash;
+        }
+    }
+
+    /// Loads a resume file dropped on the window.
+    fn load_dropped_cv(&mut self, ctx: &egui::Context) {
+        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+        for file in dropped_files {
+            if is_replay(&file.name)
+                || file
+                    .path
+                    .as_deref()
+                    .is_some_and(|p| is_replay(&p.display().to_string()))
+            {
+                // Watched by the Snake game.
+                continue;
+            }
+            if let Some(bytes) = &file.bytes {
+                let cv = match std::str::from_utf8(bytes) {
+                    Ok(content) => Cv::parse(&file.name, content),
+                    Err(e) => Err(LoadError {
+                        message: format!("{}: {e}", file.name),
+                        position: None,
+                    }),
+                };
+                self.cv_path = None;
+                self.set_cv(cv);
+            } else if let Some(path) = file.path {
+                self.cv_path = Some(path);
+                self.reload_cv();
+            }
+        }
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        #[cfg(target_arch = "wasm32")]
+        let viewing = {
+            self.follow_hash();
+            self.viewing
+        };
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 egui::widgets::global_dark_light_mode_buttons(ui);
+                self.dark_mode = ui.ctx().style().visuals.dark_mode;
+                let mut locale = self.locale.clone();
+                egui::ComboBox::from_id_source("locale")
+                    .selected_text(locale_name(&self.locale))
+                    .show_ui(ui, |ui| {
+                        for available in self.cv.locales() {
+                            ui.selectable_value(
+                                &mut locale,
+                                available.to_string(),
+                                locale_name(available),
+                            );
+                        }
+                    });
+                if locale != self.locale {
+                    self.locale = locale;
+                    self.localize();
+                }
+                ui.separator();
                 ui.selectable_value(&mut self.viewing, View::Resume, "Resume");
-                ui.selectable_value(&mut self.viewing, View::Snake, "Play a game of Snake");
-                ui.selectable_value(&mut self.viewing, View::Pong, "Play a game of Pong");
-                ui.selectable_value(&mut self.viewing, View::Maze, "Play a game of Maze");
-                ui.selectable_value(&mut self.viewing, View::Life, "Play the game of Life");
+                for (index, game) in self.games.iter().enumerate() {
+                    ui.selectable_value(&mut self.viewing, View::Game(index), game.name());
+                }
+                ui.selectable_value(&mut self.viewing, View::Controls, "Controls");
+                if self.viewing == View::Resume {
+                    ui.separator();
+                    ui.menu_button("Export", |ui| {
+                        if ui.button("Copy as JSON Resume").clicked() {
+                            let json = self.localized.to_json_resume();
+                            ui.output_mut(|o| o.copied_text = json);
+                            ui.close_menu();
+                        }
+                        if ui.button("Copy as HTML").clicked() {
+                            let html = self.localized.to_html(&self.locale);
+                            ui.output_mut(|o| o.copied_text = html);
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            ui.label("Wrap text at:");
+                            ui.add(egui::DragValue::new(&mut self.text_width).suffix(" columns"));
+                        });
+                        if ui.button("Copy as plain text").clicked() {
+                            let text = self.localized.to_text(self.text_width);
+                            ui.output_mut(|o| o.copied_text = text);
+                            ui.close_menu();
+                        }
+                        if ui.button("Copy as Markdown").clicked() {
+                            let markdown = self.localized.to_markdown(self.text_width);
+                            ui.output_mut(|o| o.copied_text = markdown);
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        #[cfg(not(target_arch = "wasm32"))]
+                        ui.horizontal(|ui| {
+                            ui.label("PDF file:");
+                            ui.text_edit_singleline(&mut self.pdf_path);
+                        });
+                        if ui.button("Export PDF").clicked() {
+                            self.export_status = Some(self.export_pdf());
+                            ui.close_menu();
+                        }
+                    });
+                    if let Some(status) = &self.export_status {
+                        ui.label(status);
+                    }
+                    if self.cv_path.is_some() && ui.button("Reload resume").clicked() {
+                        self.reload_cv();
+                    }
+                }
             });
         });
+        if self.viewing != self.shown {
+            if let View::Game(index) = self.shown {
+                self.games[index].pause();
+            }
+            self.rebinding = None;
+            self.shown = self.viewing;
+        }
         match self.viewing {
-            View::Resume => Cv::cv(ctx),
-            View::Snake => self.snake.ui(ctx, frame),
-            View::Pong => self.pong.ui(ctx, frame),
-            View::Maze => self.maze.ui(ctx, frame),
-            View::Life => self.life.ui(ctx, frame),
+            View::Resume => {
+                self.load_dropped_cv(ctx);
+                self.check_glyphs(ctx);
+                if let Some(error) = &self.cv_error {
+                    egui::TopBottomPanel::bottom("cv_error").show(ctx, |ui| {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            format!("Could not load the resume, {error}"),
+                        );
+                    });
+                }
+                self.localized.cv(ctx)
+            }
+            View::Game(index) => {
+                self.touch
+                    .update(ctx, BoardLayout::last(ctx).map(|layout| layout.rect));
+                let actions = match &mut self.gamepads {
+                    Some(gamepads) => gamepads.update().or(self.touch.frame()),
+                    None => self.touch.frame().clone(),
+                };
+                self.games[index].ui(ctx, &self.keymap, &actions);
+                self.touch.ui(ctx);
+            }
+            View::Controls => {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        self.keymap.ui(ui, &mut self.rebinding);
+                        if let Some(gamepads) = &mut self.gamepads {
+                            ui.separator();
+                            ui.heading("Controllers");
+                            gamepads.update();
+                            gamepads.ui(ui);
+                        }
+                    });
+                });
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        if self.viewing != viewing {
+            crate::common::set_location_hash(&format!("#{}", self.view_id(self.viewing)));
+            self.hash = crate::common::location_hash();
         }
     }
 }
@@ -60,4 +399,18 @@ impl eframe::App for Resume {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         self.ui(ctx, frame);
     }
+
+    /// Called by the framework to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, LOCALE_KEY, &self.locale);
+        eframe::set_value(storage, VIEW_KEY, &self.view_id(self.viewing));
+        eframe::set_value(storage, DARK_MODE_KEY, &self.dark_mode);
+        eframe::set_value(storage, KEYMAP_KEY, &self.keymap);
+        if let Some(gamepads) = &self.gamepads {
+            eframe::set_value(storage, GAMEPAD_PLAYERS_KEY, &gamepads.players);
+        }
+        for game in &self.games {
+            game.save(storage);
+        }
+    }
 }
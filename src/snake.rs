@@ -1,4 +1,5 @@
 use egui::Ui;
+use serde::{Deserialize, Serialize};
 use snake::{
     display, go_display, return_in_time, snake_generator, Cli, Difficulty, Direction, TimeCycle,
     World,
@@ -6,17 +7,45 @@ use snake::{
 use web_time::{Duration, Instant};
 use window_rs::WindowBuffer;
 
-use crate::{common::colour_changer, draw_window_buffer, InputWrapper};
+use crate::{
+    bot::{Board, Bot, Strategy},
+    common::{colour_changer, save_file, GameInput, InputWrapper, Scheduler},
+    game::Game,
+    history::History,
+    keymap::{index, ActionFrame, ACTIONS},
+    replay::{is_replay, Playback, Recording, Start, Tick, EXTENSION},
+};
 
 pub struct Snake {
     buffer: WindowBuffer,
     config: World,
     cli: Cli,
-    snake_instant: Instant,
+    /// Ticks at the speed of the snake, which grows with the difficulty.
+    scheduler: Scheduler,
     options: SnakeOptions,
     points_to_reach: usize,
+    /// The board after each tick played forward, to preview the timeline.
+    history: History,
+    /// Whether to keep going back in time until paused.
+    rewinding: bool,
+    /// The tick of `history` previewed on the timeline while paused, and its
+    /// board.
+    scrub: Option<(usize, WindowBuffer)>,
+    /// The game played so far, saved as a replay.
+    recording: Recording,
+    /// The replay watched instead of playing, driving the snakes.
+    playback: Option<Playback>,
+    replay_path: String,
+    /// Outcome of the last save or load of a replay.
+    replay_status: Option<String>,
+    /// The computer playing the second snake, if any.
+    bot: Option<Bot>,
+    /// The player 2 actions of the computer in the last tick.
+    bot_actions: ActionFrame,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SnakeOptions {
     snake_speed: usize,
     two_player: bool,
@@ -30,6 +59,105 @@ pub struct SnakeOptions {
     food_colour: u32,
     bad_berry_colour: u32,
     points_to_reach: usize,
+    width: usize,
+    height: usize,
+    speed_increase: SpeedIncrease,
+    /// The strategy of the computer playing the second snake, `None` when
+    /// it is left to a second player.
+    opponent: Option<Strategy>,
+}
+
+/// How fast the snake speeds up as it eats, [`Difficulty`] saved with the
+/// options.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpeedIncrease {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl SpeedIncrease {
+    const ALL: [SpeedIncrease; 3] = [
+        SpeedIncrease::Easy,
+        SpeedIncrease::Medium,
+        SpeedIncrease::Hard,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            SpeedIncrease::Easy => "Easy",
+            SpeedIncrease::Medium => "Medium",
+            SpeedIncrease::Hard => "Hard",
+        }
+    }
+
+    fn difficulty(self) -> Difficulty {
+        match self {
+            SpeedIncrease::Easy => Difficulty::Easy,
+            SpeedIncrease::Medium => Difficulty::Medium,
+            SpeedIncrease::Hard => Difficulty::Hard,
+        }
+    }
+}
+
+/// Board dimensions offered in the configuration panel.
+const BOARD_PRESETS: [(&str, usize, usize); 3] =
+    [("Small", 40, 25), ("Medium", 80, 50), ("Large", 120, 75)];
+const MIN_SIDE: usize = 10;
+const MAX_SIDE: usize = 200;
+
+impl SnakeOptions {
+    /// The longest snake fitting on the board when it starts, with room to
+    /// turn around.
+    fn max_snake_size(&self) -> usize {
+        self.width.min(self.height) / 2
+    }
+
+    /// Why the board can't be created with these options.
+    fn error(&self) -> Option<String> {
+        (self.snake_size > self.max_snake_size()).then(|| {
+            format!(
+                "A snake of {} cells doesn't fit on a {}x{} board, {} at most.",
+                self.snake_size,
+                self.width,
+                self.height,
+                self.max_snake_size()
+            )
+        })
+    }
+
+    /// Brings options restored from an older version or edited by hand back
+    /// in range.
+    fn clamped(mut self) -> Self {
+        self.width = self.width.clamp(MIN_SIDE, MAX_SIDE);
+        self.height = self.height.clamp(MIN_SIDE, MAX_SIDE);
+        self.snake_size = self.snake_size.min(self.max_snake_size());
+        self
+    }
+}
+
+impl Default for SnakeOptions {
+    fn default() -> Self {
+        Self {
+            snake_speed: 30,
+            two_player: false,
+            bad_berries: false,
+            snake_size: 3,
+            ghost_mode: false,
+            first_snake_colour: 0x0033CCFF,
+            first_snake_head_colour: 0x00CC66FF,
+            second_snake_colour: 0x00CC33FF,
+            second_snake_head_colour: 0x00FFCC00,
+            food_colour: 0x0066CC33,
+            bad_berry_colour: 0x00FF0000,
+            points_to_reach: 15,
+            width: 80,
+            height: 50,
+            speed_increase: SpeedIncrease::default(),
+            opponent: None,
+        }
+    }
 }
 
 impl Default for Snake {
@@ -72,27 +200,24 @@ impl Default for Snake {
             0x0066CC33,
             0x00FF0000,
         );
-        let options = SnakeOptions {
-            snake_speed: 30,
-            two_player: false,
-            bad_berries: false,
-            snake_size: 3,
-            ghost_mode: false,
-            first_snake_colour: 0x0033CCFF,
-            first_snake_head_colour: 0x00CC66FF,
-            second_snake_colour: 0x00CC33FF,
-            second_snake_head_colour: 0x00FFCC00,
-            food_colour: 0x0066CC33,
-            bad_berry_colour: 0x00FF0000,
-            points_to_reach: 15,
-        };
+        let options = SnakeOptions::default();
+        let scheduler = Scheduler::new(Duration::from_millis(cli.snake_speed as u64));
         Self {
             buffer,
             config,
             cli,
-            snake_instant: Instant::now(),
+            scheduler,
             options,
             points_to_reach: 15,
+            history: History::default(),
+            rewinding: false,
+            scrub: None,
+            recording: Recording::default(),
+            playback: None,
+            replay_path: format!("snake.{EXTENSION}"),
+            replay_status: None,
+            bot: None,
+            bot_actions: ActionFrame::default(),
         }
     }
 }
@@ -105,7 +230,12 @@ impl Snake {
         base_snake.cli.snake_size_start = self.options.snake_size;
         base_snake.cli.ghost_mode = self.options.ghost_mode;
         base_snake.cli.snake_speed = self.options.snake_speed;
-        base_snake.cli.two_players_mode = self.options.two_player;
+        base_snake.cli.two_players_mode =
+            self.options.two_player || self.options.opponent.is_some();
+        base_snake.cli.speed_increase = self.options.speed_increase.difficulty();
+        base_snake.cli.width = self.options.width;
+        base_snake.cli.height = self.options.height;
+        base_snake.buffer = WindowBuffer::new(self.options.width, self.options.height);
         base_snake.config.first_snake_colour = self.options.first_snake_colour;
         base_snake.config.second_snake_colour = self.options.second_snake_colour;
         base_snake.config.food_colour = self.options.food_colour;
@@ -113,24 +243,423 @@ impl Snake {
         base_snake.config.first_snake_head_colour = self.options.first_snake_head_colour;
         base_snake.config.second_snake_head_colour = self.options.second_snake_head_colour;
         base_snake.points_to_reach = self.options.points_to_reach;
+        base_snake.scheduler.set_clock(self.scheduler.clock());
         Self {
             buffer: base_snake.buffer,
             config: base_snake.config,
             cli: base_snake.cli,
-            snake_instant: base_snake.snake_instant,
-            options: base_snake.options,
+            scheduler: base_snake.scheduler,
+            options: self.options.clone(),
             points_to_reach: base_snake.points_to_reach,
+            history: History::default(),
+            rewinding: false,
+            scrub: None,
+            recording: Recording::default(),
+            playback: None,
+            replay_path: self.replay_path.clone(),
+            replay_status: None,
+            bot: self.options.opponent.map(Bot::new),
+            bot_actions: ActionFrame::default(),
+        }
+    }
+
+    pub fn with_options(options: SnakeOptions) -> Self {
+        let mut snake = Snake {
+            options: options.clamped(),
+            ..Snake::default()
+        };
+        snake.new_snake_w_options()
+    }
+
+    /// Steers the second snake with the player 2 actions the computer takes
+    /// on the board of this tick, like a second player would.
+    fn play_bot(&mut self) {
+        let (Some(bot), Some(snake)) = (&mut self.bot, &self.config.second_snake) else {
+            return;
+        };
+        // The snake crate only keeps the bad berries on the board it draws.
+        let mut bad_berries = Vec::new();
+        if self.cli.bad_berries {
+            for y in 0..self.buffer.height() {
+                for x in 0..self.buffer.width() {
+                    if self.buffer[(x, y)] == self.config.bad_berries_colour {
+                        bad_berries.push((x, y));
+                    }
+                }
+            }
+        }
+        let board = Board {
+            width: self.cli.width,
+            height: self.cli.height,
+            wrap: self.cli.ghost_mode,
+            snake,
+            obstacles: &self.config.snake,
+            bad_berries: &bad_berries,
+            food: self.config.food,
+        };
+        let mut down = [false; ACTIONS.len()];
+        if let Some(step) = bot.next_move(&board) {
+            down[index(&step.action())] = true;
+        }
+        self.bot_actions = self.bot_actions.next(down);
+        let _ = self.config.handle_user_input(
+            &InputWrapper::wrap(&self.bot_actions),
+            &self.cli,
+            &self.buffer,
+        );
+    }
+
+    /// Plays one tick, from the replay watched if any, and records it.
+    fn step_forward(&mut self) {
+        let replayed = match &mut self.playback {
+            Some(playback) if playback.paused => return,
+            Some(playback) => match playback.next() {
+                Some(tick) => Some(tick),
+                None => {
+                    playback.paused = true;
+                    return;
+                }
+            },
+            None => None,
+        };
+        if let Some(tick) = replayed {
+            self.config.direction = tick.first_direction();
+            self.config.second_direction = tick.second_direction();
+        }
+        // The food is only known after the tick.
+        let mut recorded = Tick::new(
+            &self.config.direction,
+            &self.config.second_direction,
+            (0, 0),
+        );
+        self.config.update(&mut self.buffer, &self.cli);
+        if let Some(tick) = replayed {
+            self.config.food = tick.food;
+        }
+        display(&self.config, &mut self.buffer, &self.cli);
+
+        recorded.food = self.config.food;
+        self.recording.ticks.push(recorded);
+        self.history.push(&self.buffer);
+    }
+
+    /// Goes back one tick, `false` when there is no tick left to undo.
+    fn step_back(&mut self) -> bool {
+        if !self.history.pop() {
+            return false;
+        }
+        // The ticks previewed past the last one are gone.
+        if self
+            .scrub
+            .as_ref()
+            .is_some_and(|(tick, _)| *tick >= self.history.len())
+        {
+            self.scrub = None;
+        }
+        self.recording.ticks.pop();
+        if let Some(playback) = &mut self.playback {
+            playback.tick = playback.tick.saturating_sub(1);
+        }
+        return_in_time(&mut self.config, &self.cli);
+        display(&self.config, &mut self.buffer, &self.cli);
+        true
+    }
+
+    /// Undoes the ticks after the one previewed on the timeline.
+    fn apply_scrub(&mut self) {
+        if let Some((tick, _)) = self.scrub.take() {
+            while self.history.len() > tick && self.step_back() {}
+        }
+    }
+
+    /// Shows the board after `tick` until the game plays again, or the board
+    /// being played from the last tick on.
+    fn preview(&mut self, tick: usize) {
+        self.scrub = None;
+        if tick < self.history.len() {
+            let (width, height) = (self.buffer.width(), self.buffer.height());
+            self.scrub = self
+                .history
+                .board(tick, width, height)
+                .map(|board| (tick, board));
+        }
+    }
+
+    fn rewind(&mut self) {
+        self.scrub = None;
+        self.rewinding = true;
+        self.config.time_cycle = TimeCycle::Backward;
+    }
+
+    fn pause_time(&mut self) {
+        self.rewinding = false;
+        self.config.time_cycle = TimeCycle::Pause;
+    }
+
+    fn play(&mut self) {
+        self.apply_scrub();
+        self.rewinding = false;
+        self.config.time_cycle = TimeCycle::Forward;
+    }
+
+    fn save_replay(&mut self) {
+        let bytes = self.recording.to_bytes(&self.options);
+        let status = match save_file(&self.replay_path, "application/json", &bytes) {
+            Ok(()) => format!("Saved {}", self.replay_path),
+            Err(e) => format!("Could not save the replay: {e}"),
+        };
+        self.replay_status = Some(status);
+    }
+
+    /// Starts watching the replay in `bytes` on a board with its options.
+    fn watch_replay(&mut self, name: &str, bytes: &[u8]) {
+        match Recording::from_bytes(bytes) {
+            Ok((options, _)) if options.bad_berries => {
+                self.replay_status = Some(format!(
+                    "Could not load {name}: games with bad berries can't be replayed"
+                ));
+            }
+            Ok((options, recording)) => {
+                let replay_path = std::mem::take(&mut self.replay_path);
+                let clock = self.scheduler.clock();
+                *self = Snake::with_options(options);
+                self.set_clock(clock);
+                self.replay_path = replay_path;
+                self.playback = Some(Playback::new(recording));
+                self.replay_status = Some(format!("Watching {name}"));
+            }
+            Err(e) => self.replay_status = Some(format!("Could not load {name}: {e}")),
+        }
+    }
+
+    /// Watches the replays dropped on the window.
+    fn load_dropped_replays(&mut self, ui: &Ui) {
+        let dropped_files = ui.input(|i| i.raw.dropped_files.clone());
+        for file in dropped_files {
+            if let Some(bytes) = &file.bytes {
+                if is_replay(&file.name) {
+                    self.watch_replay(&file.name, bytes);
+                }
+            } else if let Some(path) = file.path {
+                let name = path.display().to_string();
+                if is_replay(&name) {
+                    match std::fs::read(&path) {
+                        Ok(bytes) => self.watch_replay(&name, &bytes),
+                        Err(e) => self.replay_status = Some(format!("{name}: {e}")),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Saving the game played, or the controls of the replay watched.
+    fn replay(&mut self, ui: &mut Ui) {
+        self.load_dropped_replays(ui);
+        ui.label("Replay:");
+        if let Some(playback) = &mut self.playback {
+            ui.label(format!(
+                "Tick {} / {}",
+                playback.tick,
+                playback.recording.ticks.len()
+            ));
+            if playback.is_over() {
+                ui.label("The replay is over.");
+            }
+            ui.horizontal(|ui| {
+                let label = if playback.paused {
+                    "▶ Play"
+                } else {
+                    "⏸ Pause"
+                };
+                if ui.button(label).clicked() {
+                    playback.paused = !playback.paused;
+                }
+                ui.add(egui::Slider::new(&mut playback.speed, 0.25..=4.0).suffix("×"));
+            });
+            if ui.button("Stop watching").clicked() {
+                *self = self.new_snake_w_options();
+            }
+        } else {
+            ui.text_edit_singleline(&mut self.replay_path);
+            ui.horizontal(|ui| {
+                // The bad berries are drawn by the snake crate and not recorded.
+                let save = ui
+                    .add_enabled(!self.cli.bad_berries, egui::Button::new("Save replay"))
+                    .on_disabled_hover_text("Games with bad berries can't be replayed");
+                if save.clicked() {
+                    self.save_replay();
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button("Watch replay").clicked() {
+                    let path = self.replay_path.clone();
+                    match std::fs::read(&path) {
+                        Ok(bytes) => self.watch_replay(&path, &bytes),
+                        Err(e) => self.replay_status = Some(format!("{path}: {e}")),
+                    }
+                }
+            });
+            ui.label(format!("Drop a .{EXTENSION} file to watch it."));
+        }
+        if let Some(status) = &self.replay_status {
+            ui.label(status);
         }
     }
-    pub fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
+
+    /// Buttons moving through time and a slider over the recorded ticks.
+    fn timeline(&mut self, ui: &mut Ui) {
+        let (mode, colour) = match self.config.time_cycle {
+            TimeCycle::Forward => ("▶ Playing", ui.visuals().text_color()),
+            TimeCycle::Backward => ("⏪ Rewinding", ui.visuals().warn_fg_color),
+            TimeCycle::Pause => ("⏸ Paused", ui.visuals().warn_fg_color),
+        };
+        ui.colored_label(colour, mode);
+        ui.horizontal(|ui| {
+            if ui.button("⏪ Rewind").clicked() {
+                self.rewind();
+            }
+            if ui.button("⏸ Pause").clicked() {
+                self.pause_time();
+            }
+            if ui.button("▶ Play").clicked() {
+                self.play();
+            }
+        });
+
+        if !self.history.is_empty() {
+            let mut tick = self
+                .scrub
+                .as_ref()
+                .map_or(self.history.len(), |(tick, _)| *tick);
+            let slider = egui::Slider::new(&mut tick, 1..=self.history.len()).text("tick");
+            if ui.add(slider).changed() {
+                self.pause_time();
+                self.preview(tick);
+            }
+        }
+    }
+}
+
+impl Game for Snake {
+    fn id(&self) -> &'static str {
+        "snake"
+    }
+
+    fn name(&self) -> &'static str {
+        "Play a game of Snake"
+    }
+
+    fn handle_input(&mut self, input: &dyn GameInput) {
+        // The board is filled first, so there is a snake to steer.
+        if self.config.food == (0, 0) {
+            self.config.food_generator(&self.buffer, &self.cli)
+        };
+
+        if self.config.snake.is_empty() {
+            snake_generator(&mut self.config, &self.buffer, &self.cli);
+            match &self.playback {
+                Some(playback) => {
+                    let start = &playback.recording.start;
+                    self.config.snake = start.snake.clone();
+                    self.config.second_snake = start.second_snake.clone();
+                    self.config.food = start.food;
+                }
+                None => {
+                    self.recording.start = Start {
+                        snake: self.config.snake.clone(),
+                        second_snake: self.config.second_snake.clone(),
+                        food: self.config.food,
+                    }
+                }
+            }
+        };
+        // The recorded directions steer the snakes of a replay.
+        if self.playback.is_some() {
+            return;
+        }
+        let _ = self
+            .config
+            .handle_user_input(&InputWrapper::wrap(input), &self.cli, &self.buffer);
+    }
+
+    fn tick(&mut self) {
+        // The time keys of the snake crate end the preview like the buttons.
+        if self.config.time_cycle == TimeCycle::Forward {
+            self.apply_scrub();
+            self.rewinding = false;
+        } else if self.rewinding {
+            self.config.time_cycle = TimeCycle::Backward;
+        }
+
+        if (self.points_to_reach == self.config.score / 10)
+            || (self.points_to_reach == self.config.second_score / 10)
+        {
+            self.config.finished = true;
+        }
+
+        if self.config.time_cycle == TimeCycle::Forward {
+            if !self.config.finished {
+                if self.playback.is_none() {
+                    self.play_bot();
+                }
+                self.step_forward();
+            } else {
+                go_display(&mut self.config, &mut self.buffer, &self.cli);
+            }
+        } else if self.config.time_cycle == TimeCycle::Backward {
+            if !self.step_back() {
+                self.rewinding = false;
+            }
+            self.config.time_cycle = TimeCycle::Pause;
+        }
+        let speed = self
+            .playback
+            .as_ref()
+            .map_or(1.0, |playback| playback.speed);
+        self.scheduler.set_step(Duration::from_secs_f32(
+            self.config.snake_speed as f32 / 1000.0 / speed,
+        ));
+    }
+
+    fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    fn scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    fn configuration(&mut self, ui: &mut Ui) -> egui::Response {
         ui.vertical(|ui| {
             ui.label("Reset snake board:");
             if ui.add(egui::Button::new("Reset")).clicked() {
-                *self = Snake::default();
+                self.reset();
             };
 
             ui.separator();
 
+            self.timeline(ui);
+
+            ui.separator();
+
+            self.replay(ui);
+
+            ui.separator();
+
+            ui.label("Board size:");
+            ui.horizontal(|ui| {
+                for (name, width, height) in BOARD_PRESETS {
+                    let selected = (self.options.width, self.options.height) == (width, height);
+                    if ui.selectable_label(selected, name).clicked() {
+                        self.options.width = width;
+                        self.options.height = height;
+                    }
+                }
+            });
+            ui.add(egui::Slider::new(&mut self.options.width, MIN_SIDE..=MAX_SIDE).text("width"));
+            ui.add(egui::Slider::new(&mut self.options.height, MIN_SIDE..=MAX_SIDE).text("height"));
+
+            ui.separator();
+
             ui.label("Snake starting size:");
             ui.add(egui::Slider::new(&mut self.options.snake_size, 0..=10).suffix("pixels"));
 
@@ -146,6 +675,19 @@ impl Snake {
 
             ui.separator();
 
+            ui.label("Difficulty:");
+            ui.horizontal(|ui| {
+                for speed_increase in SpeedIncrease::ALL {
+                    ui.selectable_value(
+                        &mut self.options.speed_increase,
+                        speed_increase,
+                        speed_increase.name(),
+                    );
+                }
+            });
+
+            ui.separator();
+
             ui.label("Bad berries:");
             ui.add(egui::Checkbox::new(
                 &mut self.options.bad_berries,
@@ -162,6 +704,13 @@ impl Snake {
             ui.label("2 players:");
             ui.add(egui::Checkbox::new(&mut self.options.two_player, "Checked"));
 
+            ui.label("Second snake played by:");
+            ui.radio_value(&mut self.options.opponent, None, "Player 2");
+            for strategy in Strategy::ALL {
+                let label = format!("Computer: {}", strategy.name());
+                ui.radio_value(&mut self.options.opponent, Some(strategy), label);
+            }
+
             ui.separator();
 
             ui.label("Colour of the head of snake player 1:");
@@ -213,74 +762,165 @@ impl Snake {
             ui.separator();
 
             ui.label("Player 1 points:");
-            let mut points = self.config.score.clone() as f32;
+            let mut points = self.config.score as f32;
             let max = (self.points_to_reach as f32) * 10.0;
             ui.add(egui::Slider::new(&mut points, 0.0..=max).suffix("points"));
 
             ui.separator();
 
             ui.label("Player 2 points:");
-            let mut points = self.config.second_score.clone() as f32;
+            let mut points = self.config.second_score as f32;
             let max = (self.points_to_reach as f32) * 10.0;
             ui.add(egui::Slider::new(&mut points, 0.0..=max).suffix("points"));
 
             ui.separator();
 
             ui.label("Create snake board with all your options:");
-            if ui.add(egui::Button::new("Create")).clicked() {
+            let error = self.options.error();
+            if let Some(error) = &error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            if ui
+                .add_enabled(error.is_none(), egui::Button::new("Create"))
+                .clicked()
+            {
                 *self = self.new_snake_w_options();
             };
         })
         .response
     }
 
-    pub fn ui(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if self.config.food == (0, 0) {
-            self.config.food_generator(&self.buffer, &self.cli)
-        };
+    /// The tick previewed on the timeline, or the board being played.
+    fn buffer(&self) -> &WindowBuffer {
+        match &self.scrub {
+            Some((_, board)) => board,
+            None => &self.buffer,
+        }
+    }
 
-        if self.config.snake.is_empty() {
-            snake_generator(&mut self.config, &self.buffer, &self.cli);
+    /// The difficulty of the running game and how much faster than at the
+    /// start the snake moves.
+    fn hud(&self, ui: &mut Ui) {
+        let speed = self.config.snake_speed.max(1);
+        let level = self.cli.snake_speed as f32 / speed as f32;
+        let difficulty = match self.cli.speed_increase {
+            Difficulty::Easy => SpeedIncrease::Easy,
+            Difficulty::Medium => SpeedIncrease::Medium,
+            Difficulty::Hard => SpeedIncrease::Hard,
         };
-        ctx.input(|i| {
-            let _ =
-                self.config
-                    .handle_user_input(&InputWrapper { input: i }, &self.cli, &self.buffer);
+        ui.horizontal(|ui| {
+            ui.label(format!("Difficulty: {}", difficulty.name()));
+            ui.separator();
+            ui.label(format!("Speed: ×{level:.1} ({speed} ms per move)"));
+            ui.separator();
+            ui.label(format!("Score: {}", self.config.score / 10));
+            if self.cli.two_players_mode {
+                ui.label(format!("– {}", self.config.second_score / 10));
+            }
         });
+    }
 
-        if (self.points_to_reach == self.config.score / 10)
-            || (self.points_to_reach == self.config.second_score / 10)
-        {
-            self.config.finished = true;
-        }
+    fn reset(&mut self) {
+        let clock = self.scheduler.clock();
+        *self = Snake::default();
+        self.set_clock(clock);
+    }
 
-        if self.config.time_cycle == TimeCycle::Forward {
-            if !self.config.finished {
-                let elapsed_time = Duration::from_millis(self.config.snake_speed as u64);
+    fn load(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(options) = eframe::get_value(storage, self.id()) {
+            *self = Snake::with_options(options);
+        }
+    }
 
-                if self.snake_instant.elapsed() >= elapsed_time {
-                    self.config.update(&mut self.buffer, &self.cli);
-                    self.snake_instant = Instant::now();
-                }
-                display(&self.config, &mut self.buffer, &self.cli);
-            } else {
-                go_display(&mut self.config, &mut self.buffer, &self.cli);
-            }
-        } else if self.config.time_cycle == TimeCycle::Backward {
-            let elapsed_time = Duration::from_millis(100);
+    fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, self.id(), &self.options);
+    }
+}
 
-            if self.snake_instant.elapsed() >= elapsed_time {
-                return_in_time(&mut self.config, &self.cli);
-                self.snake_instant = Instant::now();
-            }
-            display(&self.config, &mut self.buffer, &self.cli);
-            self.config.time_cycle = TimeCycle::Pause;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{common::ManualClock, Headless};
+    use graphic::Graphic;
+    use std::rc::Rc;
+
+    #[test]
+    fn moves_one_cell_per_tick_of_its_speed() {
+        let clock = ManualClock::new();
+        let mut snake = Snake::default();
+        snake.set_clock(Rc::new(clock.clone()));
+        let mut window = Headless::new("snake", 80, 50);
+        // The first frame fills the board.
+        assert_eq!(window.play(&mut snake, &clock, Duration::ZERO), 0);
+        let (x, y) = snake.config.snake[0];
+
+        window.hold(&graphic::Key::Right);
+        for _ in 0..5 {
+            assert_eq!(
+                window.play(&mut snake, &clock, Duration::from_millis(30)),
+                1
+            );
         }
+        assert_eq!(snake.config.snake[0], ((x + 5) % 80, y));
+        assert_eq!(window.buffer(), Some(snake.buffer()));
+    }
+
+    #[test]
+    fn undoing_the_previewed_tick_ends_the_preview() {
+        let mut snake = Snake::default();
+        let mut window = Headless::new("snake", 80, 50);
+        window.hold(&graphic::Key::Right);
+        window.run(&mut snake, 5);
+        snake.pause_time();
+        snake.preview(4);
+        assert!(snake.scrub.is_some());
+
+        assert!(snake.step_back());
+        assert!(snake.scrub.is_none());
+        assert!(snake.step_back());
+        assert_eq!(snake.buffer(), &snake.buffer);
+        assert_eq!(snake.history.len(), 3);
+    }
 
-        ctx.request_repaint();
+    fn bot_game() -> (Snake, ManualClock) {
+        let options = SnakeOptions {
+            snake_size: 8,
+            opponent: Some(Strategy::SafePath),
+            ..SnakeOptions::default()
+        };
+        let mut snake = Snake::with_options(options);
+        let clock = ManualClock::new();
+        snake.set_clock(Rc::new(clock.clone()));
+        (snake, clock)
+    }
 
-        egui::SidePanel::right("Configuration").show(ctx, |ui| self.configuration(ui));
+    #[test]
+    fn the_second_snake_is_head_first() {
+        let (mut snake, _) = bot_game();
+        let mut window = Headless::new("snake", 80, 50);
+        window.step(&mut snake);
+        for _ in 0..20 {
+            let before = snake.config.second_snake.clone().unwrap();
+            window.step(&mut snake);
+            let after = snake.config.second_snake.as_ref().unwrap();
+            assert_eq!(after[1], before[0]);
+        }
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| draw_window_buffer(ui, &self.buffer));
+    #[test]
+    fn the_computer_moves_every_tick() {
+        let (mut snake, clock) = bot_game();
+        let mut window = Headless::new("snake", 80, 50);
+        window.play(&mut snake, &clock, Duration::ZERO);
+        let frame = Duration::from_millis(30) * Scheduler::MAX_TICKS;
+        for _ in 0..60 {
+            let before = snake.config.second_snake.clone().unwrap();
+            let ticks = window.play(&mut snake, &clock, frame);
+            assert_eq!(ticks, Scheduler::MAX_TICKS);
+            assert!(!snake.config.finished);
+            // The head moved one cell per tick, wherever it turned.
+            let after = snake.config.second_snake.as_ref().unwrap();
+            assert_eq!(after[ticks as usize], before[0]);
+        }
     }
 }
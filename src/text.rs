@@ -0,0 +1,352 @@
+//! Plain text and Markdown renderings of a [`Cv`] for applicant tracking
+//! systems. Sections always come in the same order: identity, summary,
+//! experience, education, projects, skills, languages and links.
+
+use crate::cv::{spans, Cv, Highlight, Labels};
+
+impl Cv {
+    /// Renders the resume as plain text wrapped at `width` columns, `0` disables wrapping.
+    pub fn to_text(&self, width: usize) -> String {
+        let mut text = String::new();
+
+        text.push_str(&self.identity.name.to_uppercase());
+        text.push('\n');
+        push_wrapped(&mut text, &self.identity.headline, width, "", "");
+
+        section(&mut text, &self.labels.summary_section);
+        for paragraph in &self.summary {
+            push_wrapped(&mut text, &plain(paragraph), width, "", "");
+            text.push('\n');
+        }
+
+        section(&mut text, &self.labels.experience_section);
+        for experience in &self.experience {
+            let title = format!(
+                "{} - {}{}",
+                experience.position,
+                experience.company,
+                period(&self.labels, &experience.start, &experience.end)
+            );
+            push_wrapped(&mut text, &title, width, "", "  ");
+            push_wrapped(&mut text, &plain(&experience.summary), width, "  ", "  ");
+            text.push('\n');
+        }
+
+        section(&mut text, &self.labels.education_section);
+        for education in &self.education {
+            let title = format!(
+                "{} - {}, {}{}",
+                education.study_type,
+                education.area,
+                education.institution,
+                period(&self.labels, &education.start, &None)
+            );
+            push_wrapped(&mut text, &title, width, "", "  ");
+            push_wrapped(&mut text, &plain(&education.summary), width, "  ", "  ");
+            text.push('\n');
+        }
+
+        section(&mut text, &self.labels.projects_section);
+        for project in &self.projects {
+            match &project.url {
+                Some(url) => text.push_str(&format!("{} ({url})\n", project.name)),
+                None => text.push_str(&format!("{}\n", project.name)),
+            }
+            if !project.description.is_empty() {
+                push_wrapped(&mut text, &plain(&project.description), width, "  ", "  ");
+            }
+            if !project.keywords.is_empty() {
+                let keywords = format!(
+                    "{}: {}",
+                    self.labels.frameworks,
+                    project.keywords.join(", ")
+                );
+                push_wrapped(&mut text, &keywords, width, "  ", "  ");
+            }
+            for highlight in &project.highlights {
+                push_wrapped(
+                    &mut text,
+                    &highlight_text(highlight, plain),
+                    width,
+                    "  - ",
+                    "    ",
+                );
+            }
+            text.push('\n');
+        }
+
+        section(&mut text, &self.labels.skills_section);
+        for group in &self.skills {
+            let skills = format!("{}: {}", group.name, group.keywords.join(", "));
+            push_wrapped(&mut text, &skills, width, "", "  ");
+        }
+
+        section(&mut text, &self.labels.languages_spoken);
+        for language in &self.languages {
+            text.push_str(&format!("{} ({})\n", language.language, language.fluency));
+        }
+
+        section(&mut text, &self.labels.social_media);
+        for link in &self.links {
+            text.push_str(&format!("{}: {}\n", link.network, link.url));
+        }
+
+        text
+    }
+
+    /// Renders the resume as Markdown wrapped at `width` columns, `0` disables wrapping.
+    pub fn to_markdown(&self, width: usize) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# {}\n\n", self.identity.name));
+        push_wrapped(&mut md, &self.identity.headline, width, "", "");
+
+        md.push_str(&format!("\n## {}\n\n", self.labels.summary_section));
+        for paragraph in &self.summary {
+            push_wrapped(&mut md, &markdown(paragraph), width, "", "");
+            md.push('\n');
+        }
+
+        md.push_str(&format!("## {}\n\n", self.labels.experience_section));
+        for experience in &self.experience {
+            md.push_str(&format!(
+                "### {}, {}{}\n\n",
+                experience.position,
+                experience.company,
+                period(&self.labels, &experience.start, &experience.end)
+            ));
+            push_wrapped(&mut md, &markdown(&experience.summary), width, "", "");
+            md.push('\n');
+        }
+
+        md.push_str(&format!("## {}\n\n", self.labels.education_section));
+        for education in &self.education {
+            md.push_str(&format!(
+                "### {}, {}{}\n\n",
+                education.study_type,
+                education.institution,
+                period(&self.labels, &education.start, &None)
+            ));
+            push_wrapped(&mut md, &markdown(&education.summary), width, "", "");
+            md.push('\n');
+        }
+
+        md.push_str(&format!("## {}\n\n", self.labels.projects_section));
+        for project in &self.projects {
+            match &project.url {
+                Some(url) => md.push_str(&format!("### [{}]({url})\n\n", project.name)),
+                None => md.push_str(&format!("### {}\n\n", project.name)),
+            }
+            if !project.description.is_empty() {
+                push_wrapped(&mut md, &markdown(&project.description), width, "", "");
+                md.push('\n');
+            }
+            if !project.keywords.is_empty() {
+                let keywords = format!(
+                    "**{}:** {}",
+                    self.labels.frameworks,
+                    project.keywords.join(", ")
+                );
+                push_wrapped(&mut md, &keywords, width, "", "");
+                md.push('\n');
+            }
+            for highlight in &project.highlights {
+                push_wrapped(
+                    &mut md,
+                    &highlight_text(highlight, markdown),
+                    width,
+                    "- ",
+                    "  ",
+                );
+            }
+            if !project.highlights.is_empty() {
+                md.push('\n');
+            }
+        }
+
+        md.push_str(&format!("## {}\n\n", self.labels.skills_section));
+        for group in &self.skills {
+            let skills = format!("**{}:** {}", group.name, group.keywords.join(", "));
+            push_wrapped(&mut md, &skills, width, "- ", "  ");
+        }
+
+        md.push_str(&format!("\n## {}\n\n", self.labels.languages_spoken));
+        for language in &self.languages {
+            md.push_str(&format!("- {} ({})\n", language.language, language.fluency));
+        }
+
+        md.push_str(&format!("\n## {}\n\n", self.labels.social_media));
+        for link in &self.links {
+            md.push_str(&format!("- [{}]({})\n", link.network, link.url));
+        }
+
+        md
+    }
+}
+
+fn section(text: &mut String, title: &str) {
+    if !text.ends_with("\n\n") {
+        text.push('\n');
+    }
+    let title = title.to_uppercase();
+    text.push_str(&title);
+    text.push('\n');
+    text.push_str(&"-".repeat(title.chars().count()));
+    text.push_str("\n\n");
+}
+
+fn period(labels: &Labels, start: &Option<String>, end: &Option<String>) -> String {
+    labels
+        .period(start, end)
+        .map(|period| format!(" ({period})"))
+        .unwrap_or_default()
+}
+
+/// Drops the `**` markers of a rich text.
+fn plain(text: &str) -> String {
+    spans(text).map(|(segment, _)| segment).collect()
+}
+
+/// Normalizes the `**` markers of a rich text, which is already Markdown.
+fn markdown(text: &str) -> String {
+    spans(text)
+        .map(|(segment, strong)| {
+            let trimmed = segment.trim();
+            if strong && !trimmed.is_empty() {
+                // Markdown only takes markers against the strong text.
+                let start = segment.len() - segment.trim_start().len();
+                let end = start + trimmed.len();
+                format!("{}**{trimmed}**{}", &segment[..start], &segment[end..])
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect()
+}
+
+fn highlight_text(highlight: &Highlight, render: fn(&str) -> String) -> String {
+    if highlight.name.is_empty() {
+        render(&highlight.description)
+    } else {
+        render(&format!(
+            "**{}:** {}",
+            highlight.name, highlight.description
+        ))
+    }
+}
+
+/// Appends `text` word-wrapped at `width` columns, its first line prefixed by
+/// `first` and the others by `rest`. Words longer than a line are not split.
+fn push_wrapped(out: &mut String, text: &str, width: usize, first: &str, rest: &str) {
+    let mut line = first.to_string();
+    let mut empty = true;
+    for word in text.split_whitespace() {
+        let len = line.chars().count();
+        if !empty && width != 0 && len + 1 + word.chars().count() > width {
+            out.push_str(&line);
+            out.push('\n');
+            line = rest.to_string();
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+        }
+        line.push_str(word);
+        empty = false;
+    }
+    out.push_str(&line);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cv::Experience;
+
+    #[test]
+    fn wraps_at_the_given_width() {
+        let paragraph =
+            "Before becoming a Rust back-end developer, I used to work in communication for years.";
+        let cv = Cv {
+            summary: vec![paragraph.to_string()],
+            experience: vec![Experience {
+                summary: paragraph.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let text = cv.to_text(30);
+        let summary: Vec<_> = text
+            .lines()
+            .skip_while(|line| *line != "SUMMARY")
+            .skip(3)
+            .take_while(|line| !line.is_empty())
+            .collect();
+        assert!(summary.len() > 1);
+        assert!(
+            summary.iter().all(|line| line.chars().count() <= 30),
+            "{summary:?}"
+        );
+        assert_eq!(summary.join(" "), paragraph);
+
+        let experience: Vec<_> = text
+            .lines()
+            .skip_while(|line| *line != "EXPERIENCE")
+            .skip(4)
+            .take_while(|line| !line.is_empty())
+            .collect();
+        assert!(experience
+            .iter()
+            .all(|line| line.starts_with("  ") && line.chars().count() <= 30));
+    }
+
+    #[test]
+    fn zero_width_does_not_wrap() {
+        let paragraph = "word ".repeat(100);
+        let cv = Cv {
+            summary: vec![paragraph.clone()],
+            ..Default::default()
+        };
+        assert!(cv.to_text(0).contains(paragraph.trim()));
+        assert!(cv.to_markdown(0).contains(paragraph.trim()));
+        assert!(!cv.to_text(20).contains(paragraph.trim()));
+    }
+
+    #[test]
+    fn sections_keep_their_order() {
+        let titles = [
+            "SUMMARY",
+            "EXPERIENCE",
+            "EDUCATION",
+            "PROJECTS",
+            "SKILLS",
+            "LANGUAGES SPOKEN",
+            "SOCIAL MEDIA",
+        ];
+        for cv in [Cv::default(), Cv::embedded().unwrap()] {
+            let text = cv.to_text(80);
+            let positions: Vec<_> = titles
+                .iter()
+                .map(|title| text.find(&format!("\n{title}\n")).unwrap())
+                .collect();
+            assert!(
+                positions.windows(2).all(|pair| pair[0] < pair[1]),
+                "{positions:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn plain_text_drops_strong_markers() {
+        let cv = Cv {
+            summary: vec!["I worked **6 years** at **Ubisoft**.".to_string()],
+            ..Default::default()
+        };
+        let text = cv.to_text(0);
+        assert!(text.contains("I worked 6 years at Ubisoft."));
+        assert!(!text.contains("**"));
+        assert!(cv
+            .to_markdown(0)
+            .contains("I worked **6 years** at **Ubisoft**."));
+    }
+}
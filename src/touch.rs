@@ -0,0 +1,253 @@
+//! On-screen controls for touch screens: a D-pad and buttons drawn over the
+//! game, and swipes on the board, triggering the same actions as player 1's
+//! controller.
+
+use egui::{Align2, Button, Event, Id, Pos2, Rect, Vec2};
+
+use crate::{
+    gamepad::{Control, Player},
+    keymap::{index, ActionFrame, ACTIONS},
+};
+
+/// Length in points a finger must travel for a swipe.
+const SWIPE_LENGTH: f32 = 30.0;
+const BUTTON_SIZE: f32 = 56.0;
+
+#[derive(Default)]
+pub struct TouchControls {
+    /// Whether the screen was touched, the controls are only shown then.
+    detected: bool,
+    /// Controls held on the overlay, read when it was last drawn.
+    held: Vec<Control>,
+    /// Where the swipe in progress started on the board.
+    swipe_start: Option<Pos2>,
+    /// Direction of the swipe that ended this frame, held for this frame only.
+    swiped: Option<Control>,
+    frame: ActionFrame,
+}
+
+impl TouchControls {
+    /// The actions triggered by the touch controls this frame.
+    pub fn frame(&self) -> &ActionFrame {
+        &self.frame
+    }
+
+    /// Reads the touches of this frame, before the game handles its input.
+    /// Swipes are only recognized when they start on `board`.
+    pub fn update(&mut self, ctx: &egui::Context, board: Option<Rect>) {
+        let touched = ctx.input(|i| i.events.iter().any(|e| matches!(e, Event::Touch { .. })));
+        self.detected |= touched;
+        if !self.detected {
+            return;
+        }
+
+        // egui forgets where the press started as soon as it is released.
+        let (pressed, released, origin, pos) = ctx.input(|i| {
+            let pointer = &i.pointer;
+            let origin = pointer.press_origin().or(pointer.interact_pos());
+            (
+                pointer.any_pressed(),
+                pointer.any_released(),
+                origin,
+                pointer.interact_pos(),
+            )
+        });
+        if pressed {
+            self.swipe_start =
+                origin.filter(|start| board.is_some_and(|board| board.contains(*start)));
+        }
+        self.swiped = None;
+        if released {
+            if let (Some(start), Some(end)) = (self.swipe_start.take(), pos) {
+                self.swiped = swipe_direction(end - start);
+            }
+        }
+
+        let mut down = [false; ACTIONS.len()];
+        for control in self.held.iter().chain(&self.swiped) {
+            for action in Player::One.actions(*control) {
+                down[index(action)] = true;
+            }
+        }
+        self.frame = self.frame.next(down);
+    }
+
+    /// Draws the D-pad at the bottom left of the screen and the buttons at
+    /// the bottom right, once the screen was touched.
+    pub fn ui(&mut self, ctx: &egui::Context) {
+        self.held.clear();
+        if !self.detected {
+            return;
+        }
+
+        egui::Area::new(Id::new("touch_dpad"))
+            .anchor(Align2::LEFT_BOTTOM, Vec2::new(16.0, -16.0))
+            .show(ctx, |ui| {
+                egui::Grid::new("dpad").spacing(Vec2::ZERO).show(ui, |ui| {
+                    ui.label("");
+                    self.button(ui, "⏶", Control::DPadUp);
+                    ui.end_row();
+                    self.button(ui, "⏴", Control::DPadLeft);
+                    ui.label("");
+                    self.button(ui, "⏵", Control::DPadRight);
+                    ui.end_row();
+                    ui.label("");
+                    self.button(ui, "⏷", Control::DPadDown);
+                    ui.end_row();
+                });
+            });
+
+        egui::Area::new(Id::new("touch_buttons"))
+            .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-16.0, -16.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    self.button(ui, "⏪", Control::LeftTrigger);
+                    self.button(ui, "⏩", Control::RightTrigger);
+                    self.button(ui, "B", Control::East);
+                    self.button(ui, "A", Control::South);
+                });
+            });
+    }
+
+    fn button(&mut self, ui: &mut egui::Ui, text: &str, control: Control) {
+        let response = ui.add(Button::new(text).min_size(Vec2::splat(BUTTON_SIZE)));
+        if response.is_pointer_button_down_on() {
+            self.held.push(control);
+        }
+    }
+}
+
+/// The D-pad direction of a swipe moving by `delta`, along its longest axis.
+fn swipe_direction(delta: Vec2) -> Option<Control> {
+    if delta.length() < SWIPE_LENGTH {
+        return None;
+    }
+    let direction = if delta.x.abs() > delta.y.abs() {
+        if delta.x > 0.0 {
+            Control::DPadRight
+        } else {
+            Control::DPadLeft
+        }
+    } else if delta.y > 0.0 {
+        Control::DPadDown
+    } else {
+        Control::DPadUp
+    };
+    Some(direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{PointerButton, RawInput, TouchDeviceId, TouchId, TouchPhase};
+
+    #[test]
+    fn swipes_follow_their_longest_axis() {
+        assert_eq!(
+            swipe_direction(Vec2::new(40.0, 10.0)),
+            Some(Control::DPadRight)
+        );
+        assert_eq!(
+            swipe_direction(Vec2::new(-40.0, 39.0)),
+            Some(Control::DPadLeft)
+        );
+        assert_eq!(
+            swipe_direction(Vec2::new(5.0, 31.0)),
+            Some(Control::DPadDown)
+        );
+        assert_eq!(
+            swipe_direction(Vec2::new(-30.0, -30.0)),
+            Some(Control::DPadUp)
+        );
+        assert_eq!(swipe_direction(Vec2::new(20.0, -20.0)), None);
+    }
+
+    fn touch(pos: Pos2, phase: TouchPhase) -> Event {
+        Event::Touch {
+            device_id: TouchDeviceId(0),
+            id: TouchId(0),
+            phase,
+            pos,
+            force: None,
+        }
+    }
+
+    fn button(pos: Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        }
+    }
+
+    fn frame(ctx: &egui::Context, controls: &mut TouchControls, events: Vec<Event>) -> ActionFrame {
+        let board = Rect::from_min_size(Pos2::ZERO, Vec2::splat(100.0));
+        ctx.begin_frame(RawInput {
+            events,
+            ..Default::default()
+        });
+        controls.update(ctx, Some(board));
+        let _ = ctx.end_frame();
+        controls.frame().clone()
+    }
+
+    fn swipe(
+        ctx: &egui::Context,
+        controls: &mut TouchControls,
+        from: Pos2,
+        to: Pos2,
+    ) -> ActionFrame {
+        frame(
+            ctx,
+            controls,
+            vec![
+                touch(from, TouchPhase::Start),
+                Event::PointerMoved(from),
+                button(from, true),
+            ],
+        );
+        frame(
+            ctx,
+            controls,
+            vec![touch(to, TouchPhase::Move), Event::PointerMoved(to)],
+        );
+        frame(
+            ctx,
+            controls,
+            vec![
+                touch(to, TouchPhase::End),
+                button(to, false),
+                Event::PointerGone,
+            ],
+        )
+    }
+
+    #[test]
+    fn swipes_on_the_board_trigger_one_frame() {
+        let ctx = egui::Context::default();
+        let mut controls = TouchControls::default();
+        let actions = swipe(
+            &ctx,
+            &mut controls,
+            Pos2::new(10.0, 50.0),
+            Pos2::new(60.0, 55.0),
+        );
+        assert!(actions.is_pressed(&graphic::Key::Right));
+        let actions = frame(&ctx, &mut controls, Vec::new());
+        assert!(actions.is_released(&graphic::Key::Right));
+    }
+
+    #[test]
+    fn swipes_off_the_board_are_ignored() {
+        let ctx = egui::Context::default();
+        let mut controls = TouchControls::default();
+        let actions = swipe(
+            &ctx,
+            &mut controls,
+            Pos2::new(150.0, 50.0),
+            Pos2::new(50.0, 50.0),
+        );
+        assert!(!actions.is_down(&graphic::Key::Left));
+    }
+}
@@ -0,0 +1,31 @@
+#!/usr/bin/env bash
+# Builds assets/fonts/NotoSansJP-Subset.otf, the CJK fallback font embedded by
+# the `cjk_font` feature, from the characters used in assets/resume.toml.
+#
+# Usage: ./subset_font.sh path/to/NotoSansJP-Regular.otf
+# The full font is available at https://fonts.google.com/noto/specimen/Noto+Sans+JP
+# and pyftsubset comes with `pip install fonttools`.
+set -eu
+
+font="$1"
+out="assets/fonts/NotoSansJP-Subset.otf"
+
+mkdir -p assets/fonts
+pyftsubset "$font" \
+    --text-file=assets/resume.toml \
+    --layout-features='*' \
+    --no-hinting \
+    --output-file="$out"
+
+# Every character of the resume must be in the subset.
+python3 - "$out" <<'PY'
+import sys
+from fontTools.ttLib import TTFont
+
+cmap = TTFont(sys.argv[1]).getBestCmap()
+text = open("assets/resume.toml", encoding="utf-8").read()
+missing = sorted({c for c in text if ord(c) > 0x7f and ord(c) not in cmap})
+if missing:
+    sys.exit("Missing glyphs: " + "".join(missing))
+print("All", len({c for c in text if ord(c) > 0x7f}), "non-ASCII characters are covered")
+PY
@@ -0,0 +1,129 @@
+use graphic::Graphic;
+use resume::{registry, Game, Headless, ManualClock};
+use std::rc::Rc;
+use web_time::Duration;
+use window_rs::WindowBuffer;
+
+/// The game of the registry with `id`, timed by a clock moved by hand.
+fn game(id: &str) -> (Box<dyn Game>, ManualClock) {
+    let mut game = registry()
+        .into_iter()
+        .find(|game| game.id() == id)
+        .expect("the game is registered");
+    let clock = ManualClock::new();
+    game.set_clock(Rc::new(clock.clone()));
+    (game, clock)
+}
+
+/// The cells of `buffer` with `colour`.
+fn cells(buffer: &WindowBuffer, colour: u32) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    for y in 0..buffer.height() {
+        for x in 0..buffer.width() {
+            if buffer[(x, y)] == colour {
+                cells.push((x, y));
+            }
+        }
+    }
+    cells
+}
+
+#[test]
+fn holding_right_moves_the_snake_head() {
+    /// The head colour of the default options.
+    const HEAD: u32 = 0x00CC66FF;
+    let (mut snake, clock) = game("snake");
+    let mut window = Headless::new("snake", 80, 50);
+    // The first frame fills the board without ticking.
+    assert_eq!(window.play(snake.as_mut(), &clock, Duration::ZERO), 0);
+    let [(x, y)] = cells(snake.buffer(), HEAD)[..] else {
+        panic!("one head is drawn");
+    };
+
+    window.hold(&graphic::Key::Right);
+    for _ in 0..5 {
+        window.play(snake.as_mut(), &clock, Duration::from_millis(30));
+    }
+    let buffer = window.buffer().expect("a buffer was drawn");
+    assert_eq!(cells(buffer, HEAD), [((x + 5) % buffer.width(), y)]);
+}
+
+#[test]
+fn a_click_brings_a_life_cell_to_life() {
+    let (mut life, clock) = game("life");
+    let mut window = Headless::new("life", 30, 30);
+    window.play(life.as_mut(), &clock, Duration::ZERO);
+    assert_eq!(life.buffer()[(4, 7)], 0);
+
+    // No generation is due, only the click changes the board.
+    window.click(&graphic::Mouse::Left, (4, 7));
+    assert_eq!(window.play(life.as_mut(), &clock, Duration::ZERO), 0);
+    assert_ne!(life.buffer()[(4, 7)], 0);
+}
+
+#[test]
+fn pong_draws_both_paddles_and_the_ball() {
+    /// The colours of the default options.
+    const PLAYER_1: u32 = 0x00FF0000;
+    const PLAYER_2: u32 = 0xFF00FF00;
+    const BALL: u32 = 0xFFFFFF00;
+    let (mut pong, clock) = game("pong");
+    let mut window = Headless::new("pong", 50, 33);
+    assert!(window.play(pong.as_mut(), &clock, Duration::from_millis(20)) > 0);
+
+    let buffer = window.buffer().expect("a buffer was drawn");
+    let (left, right) = (cells(buffer, PLAYER_1), cells(buffer, PLAYER_2));
+    assert!(!left.is_empty() && !right.is_empty());
+    assert!(left.iter().all(|&(x, _)| x < buffer.width() / 2));
+    assert!(right.iter().all(|&(x, _)| x >= buffer.width() / 2));
+    assert_eq!(cells(buffer, BALL).len(), 1);
+}
+
+#[test]
+fn the_maze_player_stays_still_without_input() {
+    let (mut maze, clock) = game("maze");
+    let mut window = Headless::new("maze", 30, 30);
+    assert_eq!(
+        window.play(maze.as_mut(), &clock, Duration::from_millis(10)),
+        1
+    );
+    let board = maze.buffer().clone();
+    let colours: std::collections::BTreeSet<u32> = (0..board.height())
+        .flat_map(|y| (0..board.width()).map(move |x| (x, y)))
+        .map(|cell| board[cell])
+        .collect();
+    assert!(colours.len() > 1, "the walls and the paths are drawn");
+
+    assert_eq!(
+        window.play(maze.as_mut(), &clock, Duration::from_millis(100)),
+        10
+    );
+    assert_eq!(maze.buffer(), &board);
+}
+
+#[test]
+fn held_keys_are_pressed_once() {
+    let mut window = Headless::new("keys", 80, 50);
+    window.hold(&graphic::Key::Up);
+    window.next_frame();
+    assert!(window.is_key_pressed(graphic::Key::Up));
+    assert!(window.is_key_down(graphic::Key::Up));
+    window.next_frame();
+    assert!(!window.is_key_pressed(graphic::Key::Up));
+    assert!(window.is_key_down(graphic::Key::Up));
+    window.release(&graphic::Key::Up);
+    window.next_frame();
+    assert!(!window.is_key_down(graphic::Key::Up));
+    assert_eq!(window.get_keys_released().len(), 1);
+}
+
+#[test]
+fn clicks_last_one_frame() {
+    let mut window = Headless::new("mouse", 80, 50);
+    window.click(&graphic::Mouse::Left, (3, 4));
+    window.next_frame();
+    assert_eq!(window.get_mouse_pos(graphic::Mouse::Left), Some((3.0, 4.0)));
+    assert!(!window.get_mouse_down(graphic::Mouse::Right));
+    window.next_frame();
+    assert_eq!(window.get_mouse_pos(graphic::Mouse::Left), None);
+}